@@ -0,0 +1,206 @@
+//! ## sundial
+//! 校验与预览cron表达式、检查任务定义文件的命令行工具，需要开启`cli` feature
+//!
+//! ```text
+//! sundial validate "0/1 * * * * ?"
+//! sundial next "0 0 9 * * ?" -n 10 --tz Asia/Shanghai
+//! sundial check jobs.toml
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use chrono::{DateTime, Local};
+use clap::{Parser, Subcommand};
+use lynn_sundial::schedule_api::{JobDefinition, Schedule, TaskTimeZone};
+
+/// cron表达式的字段名称与取值范围，年份可以省略
+const FIELDS: [(&str, &str); 7] = [
+    ("seconds", "0-59"),
+    ("minutes", "0-59"),
+    ("hours", "0-23"),
+    ("day of month", "1-31"),
+    ("month", "1-12 or JAN-DEC"),
+    ("day of week", "1-7 or SUN-SAT, 1 is Sunday"),
+    ("year", "1970-2100"),
+];
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %a %H:%M:%S %:z";
+
+/// Validate and preview cron schedules used by lynn_sundial
+#[derive(Parser)]
+#[command(name = "sundial", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check a cron expression and explain what is wrong with it
+    Validate {
+        /// Cron expression, e.g. "0 0/5 * * * ?"
+        expr: String,
+    },
+    /// Print the upcoming fire times of a cron expression
+    Next {
+        /// Cron expression, e.g. "0 0/5 * * * ?"
+        expr: String,
+        /// Number of fire times to print
+        #[arg(short = 'n', default_value_t = 10)]
+        count: usize,
+        /// Time zone: Local, UTC, +08:00 or an IANA name such as Asia/Shanghai
+        #[arg(long, default_value = "Local")]
+        tz: TaskTimeZone,
+    },
+    /// Lint a TOML/JSON job definition file
+    Check {
+        /// Job definition file, parsed as JSON when the extension is `json`
+        path: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Validate { expr } => validate(&expr),
+        Command::Next { expr, count, tz } => next(&expr, count, tz),
+        Command::Check { path } => check(&path),
+    }
+}
+
+fn validate(expr: &str) -> ExitCode {
+    let schedule = match Schedule::from_str(expr) {
+        Ok(schedule) => schedule,
+        Err(error) => return invalid(expr, &error),
+    };
+    println!("`{expr}` is valid");
+    // `@daily`等简写没有字段
+    if !expr.trim_start().starts_with('@') {
+        for ((name, _), value) in FIELDS.iter().zip(expr.split_whitespace()) {
+            println!("  {name:<14}{value:<12}{}", describe(value));
+        }
+    }
+    println!("next fire times (Local):");
+    print_upcoming(expr, &schedule, &TaskTimeZone::Local, 3)
+}
+
+fn next(expr: &str, count: usize, tz: TaskTimeZone) -> ExitCode {
+    match Schedule::from_str(expr) {
+        Ok(schedule) => print_upcoming(expr, &schedule, &tz, count),
+        Err(error) => invalid(expr, &error),
+    }
+}
+
+/// 任务函数在代码中注册，这里不检查`handler`
+fn check(path: &Path) -> ExitCode {
+    let jobs = match JobDefinition::load(path) {
+        Ok(jobs) => jobs,
+        Err(error) => {
+            eprintln!("error: {}: {error}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut problems = JobDefinition::check(&jobs, None);
+    let now = Local::now();
+    for job in &jobs {
+        let Ok(schedule) = job.schedule() else {
+            continue;
+        };
+        let tz = job.timezone.unwrap_or_default();
+        match schedule.upcoming(&tz, &now, 1).first() {
+            Some(next_fire) => println!(
+                "  {:<24}{:<24}next {}",
+                job.name,
+                job.cron,
+                format_datetime(&tz, next_fire)
+            ),
+            None => problems.push(format!(
+                "{}: cron `{}` never fires again",
+                job.name, job.cron
+            )),
+        }
+    }
+    if !problems.is_empty() {
+        for problem in problems {
+            eprintln!("error: {problem}");
+        }
+        return ExitCode::FAILURE;
+    }
+    println!("{}: {} jobs ok", path.display(), jobs.len());
+    ExitCode::SUCCESS
+}
+
+/// 错误都输出到stderr并以非0退出
+fn invalid(expr: &str, error: &cron::error::Error) -> ExitCode {
+    eprintln!("error: `{expr}` is invalid");
+    for line in explain(expr, error) {
+        eprintln!("  {line}");
+    }
+    ExitCode::FAILURE
+}
+
+/// 与`check`相同，不会再触发的表达式视为错误
+fn print_upcoming(expr: &str, schedule: &Schedule, tz: &TaskTimeZone, count: usize) -> ExitCode {
+    let upcoming = schedule.upcoming(tz, &Local::now(), count);
+    if upcoming.is_empty() {
+        eprintln!("error: `{expr}` never fires again");
+        return ExitCode::FAILURE;
+    }
+    for datetime in upcoming {
+        println!("  {}", format_datetime(tz, &datetime));
+    }
+    ExitCode::SUCCESS
+}
+
+fn format_datetime(tz: &TaskTimeZone, datetime: &DateTime<Local>) -> String {
+    tz.convert(datetime).format(DATETIME_FORMAT).to_string()
+}
+
+/// 说明cron表达式不合法的原因
+/// cron的错误信息只指出解析失败的位置，这里再检查字段数量，并逐个字段找出不合法的字段
+fn explain(expr: &str, error: &cron::error::Error) -> Vec<String> {
+    let mut lines: Vec<String> = error.to_string().lines().map(str::to_string).collect();
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    match fields.len() {
+        5 => lines.push(format!(
+            "found 5 fields, but expressions start with a seconds field, e.g. `0 {}`",
+            fields.join(" ")
+        )),
+        6 | 7 => {
+            for (index, value) in fields.iter().enumerate() {
+                // 其余字段为`*`时仍然不合法，说明是该字段的问题
+                let mut probe = vec!["*"; fields.len()];
+                probe[index] = value;
+                if Schedule::from_str(&probe.join(" ")).is_err() {
+                    let (name, range) = FIELDS[index];
+                    lines.push(format!("{name} `{value}` is invalid, expected {range}"));
+                }
+            }
+        }
+        count => lines.push(format!(
+            "expected 6 or 7 fields (seconds minutes hours day-of-month month day-of-week [year]), found {count}"
+        )),
+    }
+    lines
+}
+
+/// 字段的含义，如`0/5`为从0开始每5个单位
+fn describe(value: &str) -> String {
+    if let Some((start, step)) = value.split_once('/') {
+        return match start {
+            "*" => format!("every {step}"),
+            _ if start.contains('-') => format!("every {step} within {start}"),
+            _ => format!("every {step} starting at {start}"),
+        };
+    }
+    match value {
+        "*" => "every".to_string(),
+        "?" => "any".to_string(),
+        _ if value.contains(',') => format!("one of {value}"),
+        _ if value.contains('-') => format!("from {}", value.replacen('-', " to ", 1)),
+        _ => format!("at {value}"),
+    }
+}
@@ -30,12 +30,12 @@
 //!
 //! 使用 `cargo add lynn_sundial` 或者在`Cargo.toml`添加如下:
 //!
-//! ```rust
+//! ```toml
 //! [dependencies]
 //! lynn_sundial = "1"
 //! ```
 //!
-//! ```rust
+//! ```rust,no_run
 //! use chrono::Local;
 //! use lynn_sundial::schedule_api::*;
 
@@ -111,6 +111,745 @@
 //! ......
 //! */
 //! ```
+//!
+//! #### 任务上下文
+//!
+//! 任务函数可以接收一个`TaskContext`参数，用于获取任务id、计划触发时间、实际开始时间以及运行次数
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn report(context: TaskContext) {
+//!     println!(
+//!         "task {} run #{} scheduled at {}, started at {}",
+//!         context.task_id(),
+//!         context.run_count(),
+//!         context.scheduled_time(),
+//!         context.start_time(),
+//!     );
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let _ = scheduler.push_task("0/5 * * * * ?", report, RepeatModel::Repetition);
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 共享资源
+//!
+//! 通过`insert_resource`插入共享资源后，任务函数可以像Bevy的system一样声明`Res<R>`、`TaskId`、`TaskContext`等参数，由调度器在每次运行时自动填充
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! struct DbPool(String);
+//!
+//! async fn cleanup(db: Res<DbPool>, task_id: TaskId) {
+//!     println!("task {} cleanup with {}", task_id, db.0);
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     // 资源需要在新增任务之前插入，缺失时新增任务会返回错误
+//!     scheduler.insert_resource(DbPool("postgres://localhost".to_string()));
+//!     let _ = scheduler.push_task("0 0/1 * * * ?", cleanup, RepeatModel::Repetition);
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 可失败的任务
+//!
+//! 任务函数可以返回`Result<(), E>`（`E`需要实现`Display`），返回`Err`时会计入该任务的运行统计，可以通过`task_stats`/`failing_tasks`查询
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn sync_orders() -> Result<(), std::io::Error> {
+//!     Err(std::io::Error::other("upstream unavailable"))
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let task_id = scheduler
+//!         .push_task("0/10 * * * * ?", sync_orders, RepeatModel::Repetition)
+//!         .unwrap();
+//!     tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+//!     if let Some(stats) = scheduler.task_stats(task_id) {
+//!         println!(
+//!             "failed {}/{} times, last error: {:?}",
+//!             stats.failure_count, stats.run_count, stats.last_error
+//!         );
+//!     }
+//!     for (task_id, stats) in scheduler.failing_tasks() {
+//!         println!("task {task_id} is failing: {:?}", stats.last_error);
+//!     }
+//! }
+//! ```
+//!
+//! #### 失败重试
+//!
+//! 通过`TaskOptions`为任务设置`RetryPolicy`，任务函数返回`Err`后，调度器会按退避策略将本次运行作为一次性任务重新放入时间轮，不影响任务原有的cron节奏，任务函数可以通过`TaskContext::attempt`获取当前是第几次尝试
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn push_report(context: TaskContext) -> Result<(), String> {
+//!     println!("run #{} attempt {}", context.run_count(), context.attempt());
+//!     Err("remote server busy".to_string())
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     // 最多尝试5次，重试间隔依次为1s、2s、4s、8s，并额外增加0~500ms的随机延迟
+//!     let retry_policy =
+//!         RetryPolicy::exponential(5, Duration::from_secs(1), Duration::from_secs(30))
+//!             .with_jitter(Duration::from_millis(500));
+//!     let _ = scheduler.push_order_task_with_options(
+//!         "0 0/5 * * * ?",
+//!         push_report,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new().retry(retry_policy),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 运行超时
+//!
+//! 通过`TaskOptions::timeout`为单个任务设置超时时间，或通过`set_default_timeout`设置默认超时时间。超时后本次运行会被取消并记为`TaskRunResult::TimedOut`，有序任务的下一次运行不会再被卡住的任务阻塞
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn export_data() {
+//!     tokio::time::sleep(Duration::from_secs(3600)).await;
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     scheduler.set_default_timeout(Some(Duration::from_secs(60)));
+//!     let _ = scheduler.push_order_task_with_options(
+//!         "0 0/1 * * * ?",
+//!         export_data,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new().timeout(Duration::from_secs(10)),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 时区
+//!
+//! cron表达式默认按系统本地时区解析。通过`TaskOptions::time_zone`为单个任务设置时区，或通过`set_default_time_zone`设置默认时区。支持`TaskTimeZone::Local`、`TaskTimeZone::Utc`与固定偏移量；开启`tz` feature后支持`Asia/Shanghai`形式的IANA时区，并正确处理夏令时：重复出现的本地时间只触发一次，不存在的本地时间会被跳过
+//!
+//! ```toml
+//! [dependencies]
+//! lynn_sundial = { version = "1", features = ["tz"] }
+//! ```
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn daily_report() {
+//!     println!("每天纽约时间9点运行");
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     scheduler.set_default_time_zone(TaskTimeZone::Utc);
+//!     let _ = scheduler.push_order_task_with_options(
+//!         "0 0 9 * * ?",
+//!         daily_report,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new().time_zone("America/New_York".parse::<TaskTimeZone>().unwrap()),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 测试时钟
+//!
+//! 调度器默认使用系统时钟`SystemClock`。通过`Scheduler::with_clock`注入`TestClock`后，时间只会在调用`advance`时前进，无需真正等到凌晨3点就可以测试`0 0 3 * * ?`这样的任务
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::time::Duration;
+//! use chrono::{Local, TimeZone};
+//! use lynn_sundial::schedule_api::*;
+//!
+//! static RUNS: AtomicUsize = AtomicUsize::new(0);
+//!
+//! async fn nightly_job() {
+//!     RUNS.fetch_add(1, Ordering::SeqCst);
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let clock = TestClock::new(Local.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+//!     let mut scheduler = Scheduler::with_clock(clock.clone());
+//!     let _ = scheduler.push_task("0 0 3 * * ?", nightly_job, RepeatModel::Repetition);
+//!     for _ in 0..48 {
+//!         // 每次推进1小时，advance返回时到期的任务已经分发
+//!         clock.advance(Duration::from_secs(60 * 60)).await;
+//!     }
+//!     // 等待任务函数运行结束
+//!     tokio::time::sleep(Duration::from_millis(100)).await;
+//!     assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+//! }
+//! ```
+//!
+//! #### 调度器配置
+//!
+//! 通过`Scheduler::builder()`设置tick间隔、副reactor数量、channel大小以及各层时间轮的槽位数量与间隔，配置不合法时`build`返回错误
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn sync_orders() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // 低流量服务：2个副reactor，100ms tick一次
+//!     let mut scheduler = Scheduler::builder()
+//!         .task_pool_size(2)
+//!         .tick_time(Duration::from_millis(100))
+//!         .build()
+//!         .unwrap();
+//!     let _ = scheduler.push_disorder_task("0/5 * * * * ?", sync_orders, RepeatModel::Repetition);
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 远期任务
+//!
+//! 超出小时时间轮范围（默认24小时）的任务，如每周、每月、每年的任务，会按目标时间存放在溢出队列中，进入小时时间轮的范围后再逐层降级，准时触发
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::time::Duration;
+//! use chrono::{Local, TimeZone};
+//! use lynn_sundial::schedule_api::*;
+//!
+//! static WEEKLY: AtomicUsize = AtomicUsize::new(0);
+//! static MONTHLY: AtomicUsize = AtomicUsize::new(0);
+//! static YEARLY: AtomicUsize = AtomicUsize::new(0);
+//!
+//! async fn weekly() { WEEKLY.fetch_add(1, Ordering::SeqCst); }
+//! async fn monthly() { MONTHLY.fetch_add(1, Ordering::SeqCst); }
+//! async fn yearly() { YEARLY.fetch_add(1, Ordering::SeqCst); }
+//!
+//! fn runs() -> (usize, usize, usize) {
+//!     (WEEKLY.load(Ordering::SeqCst), MONTHLY.load(Ordering::SeqCst), YEARLY.load(Ordering::SeqCst))
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // 2025-01-01是星期三
+//!     let clock = TestClock::new(Local.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+//!     let mut scheduler = Scheduler::with_clock(clock.clone());
+//!     let _ = scheduler.push_task("0 0 0 ? * WED", weekly, RepeatModel::Once);
+//!     let _ = scheduler.push_task("0 0 0 1 * ?", monthly, RepeatModel::Once);
+//!     let _ = scheduler.push_task("0 0 0 1 1 ?", yearly, RepeatModel::Once);
+//!     let day = Duration::from_secs(24 * 60 * 60);
+//!     let second = Duration::from_secs(1);
+//!
+//!     // 7天后
+//!     clock.advance(day * 7 - second).await;
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(runs(), (0, 0, 0));
+//!     clock.advance(second).await;
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(runs(), (1, 0, 0));
+//!
+//!     // 31天后
+//!     clock.advance(day * 24 - second).await;
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(runs(), (1, 0, 0));
+//!     clock.advance(second).await;
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(runs(), (1, 1, 0));
+//!
+//!     // 1年后
+//!     clock.advance(day * 334 - second).await;
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(runs(), (1, 1, 0));
+//!     clock.advance(second).await;
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(runs(), (1, 1, 1));
+//! }
+//! ```
+//!
+//! #### 事件订阅
+//!
+//! 通过`subscribe`订阅调度器事件，包括任务的新增、触发（计划时间与实际时间）、运行成功/失败、暂停、恢复、销毁、cron更新以及错过触发（实际触发时间晚于计划时间超过阈值，默认1秒）
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn heartbeat() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let mut events = scheduler.subscribe();
+//!     tokio::spawn(async move {
+//!         while let Ok(event) = events.recv().await {
+//!             match event {
+//!                 SchedulerEvent::Failed { task_id, error, .. } => {
+//!                     eprintln!("任务{task_id}运行失败：{error}");
+//!                 }
+//!                 SchedulerEvent::Misfired { task_id, scheduled, actual, .. } => {
+//!                     eprintln!("任务{task_id}计划于{scheduled}触发，实际于{actual}触发");
+//!                 }
+//!                 _ => {}
+//!             }
+//!         }
+//!     });
+//!     let _ = scheduler.push_task("0/5 * * * * ?", heartbeat, RepeatModel::Repetition);
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 指标
+//!
+//! 开启`metrics` feature后，通过`metrics_snapshot`获取触发、成功、失败、错过触发的次数，触发延迟与运行时长的直方图，各层时间轮中的任务数量以及等待运行的无序任务数量，并通过`render_prometheus`渲染为Prometheus文本格式
+//!
+//! ```toml
+//! [dependencies]
+//! lynn_sundial = { version = "1", features = ["metrics"] }
+//! ```
+//!
+//! ```rust,ignore
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn heartbeat() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let _ = scheduler.push_task("0/5 * * * * ?", heartbeat, RepeatModel::Repetition);
+//!     // 在已有的HTTP接口（如`/metrics`）中返回
+//!     let body = scheduler.metrics_snapshot().render_prometheus();
+//!     println!("{body}");
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### tracing
+//!
+//! 开启`tracing` feature后，每次运行都在名为`task_run`的span内执行，span包含`task_id`、`task_name`（通过`TaskOptions::name`设置的名称，未设置时为任务函数的类型名）、`order_type`、`scheduled_time`、`run_count`与`attempt`；错过触发、时间轮之间的降级、任务状态变更、运行失败与重试也会记录为tracing事件
+//!
+//! ```toml
+//! [dependencies]
+//! lynn_sundial = { version = "1", features = ["tracing"] }
+//! ```
+//!
+//! #### 停止
+//!
+//! 通过`shutdown`停止调度器，`ShutdownMode::Drain`不再触发新的任务，等待已经触发的任务运行结束，超过`deadline`后中止剩余的任务；`ShutdownMode::Immediate`立即中止所有任务。停止完成后`wait_all`返回
+//!
+//! 需要在其他异步任务中停止时，通过`shutdown_handle`获取停止句柄。调度器被释放时会立即中止所有内部任务
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn heartbeat() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let _ = scheduler.push_task("0/1 * * * * ?", heartbeat, RepeatModel::Repetition);
+//!     let shutdown_handle = scheduler.shutdown_handle();
+//!     tokio::spawn(async move {
+//!         // 实际使用时可以等待tokio::signal::ctrl_c()
+//!         tokio::time::sleep(Duration::from_millis(1500)).await;
+//!         shutdown_handle
+//!             .shutdown(ShutdownMode::Drain {
+//!                 deadline: Duration::from_secs(10),
+//!             })
+//!             .await;
+//!     });
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 任务查询
+//!
+//! 通过`list_tasks`、`task_info`查询任务的名称、cron、有序/无序、状态、剩余运行次数、下一次与最近一次触发时间以及最近一次运行结果，通过`upcoming`按cron计算之后的触发时间
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn cleanup() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let task_id = scheduler
+//!         .push_task("0 0 3 * * ?", cleanup, RepeatModel::Times(3))
+//!         .unwrap();
+//!     for task_info in scheduler.list_tasks() {
+//!         println!("{} {} {:?}", task_info.id, task_info.cron, task_info.next_fire);
+//!     }
+//!     // 剩余3次，最多返回3个时间
+//!     let upcoming = scheduler.upcoming(task_id, 5).unwrap();
+//!     assert_eq!(upcoming.len(), 3);
+//! }
+//! ```
+//!
+//! #### 名称与标签
+//!
+//! 通过`TaskOptions::name`设置唯一的任务名称，通过`TaskOptions::tag`/`tags`添加标签。名称重复时新增任务返回错误，`task_id_by_name`按名称查询任务id，`pause_by_tag`、`restart_by_tag`、`destory_by_tag`、`update_cron_by_tag`批量操作带有某个标签的任务
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn charge() {}
+//! async fn refund() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     scheduler
+//!         .push_order_task_with_options(
+//!             "0 0 1 * * ?",
+//!             charge,
+//!             RepeatModel::Repetition,
+//!             TaskOptions::new().name("charge").tag("billing"),
+//!         )
+//!         .unwrap();
+//!     scheduler
+//!         .push_order_task_with_options(
+//!             "0 30 1 * * ?",
+//!             refund,
+//!             RepeatModel::Repetition,
+//!             TaskOptions::new().name("refund").tag("billing"),
+//!         )
+//!         .unwrap();
+//!     assert!(scheduler.task_id_by_name("charge").is_some());
+//!     // 迁移期间暂停所有billing任务
+//!     assert_eq!(scheduler.pause_by_tag("billing").await.len(), 2);
+//!     assert_eq!(scheduler.restart_by_tag("billing").await.len(), 2);
+//! }
+//! ```
+//!
+//! #### 重叠策略
+//!
+//! 通过`TaskOptions::overlap_policy`设置任务触发时上一次运行尚未结束的处理方式：
+//!
+//! - `OverlapPolicy::Queue`：排队等待，等同于有序任务（`push_order_task`的默认值）
+//! - `OverlapPolicy::Parallel`：直接并行运行，等同于无序任务（`push_disorder_task`的默认值）
+//! - `OverlapPolicy::Skip`：跳过本次触发，并发送`SchedulerEvent::Skipped`事件
+//! - `OverlapPolicy::Replace`：取消上一次运行后运行本次触发，被取消的运行记为`TaskRunResult::Cancelled`，并发送`SchedulerEvent::Cancelled`事件
+//!
+//! 运行从触发时起视为尚未结束，排队等待副reactor的运行同样会被跳过或取消。
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn sync_orders() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let _ = scheduler.push_order_task_with_options(
+//!         "0/10 * * * * ?",
+//!         sync_orders,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new().overlap_policy(OverlapPolicy::Skip),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 并发上限
+//!
+//! 无序任务的运行时间长于cron间隔时，同一任务会同时运行多次。通过`TaskOptions::max_concurrent`限制同时运行的数量，达到上限时：
+//!
+//! - `LimitBehavior::Wait`：等待正在运行的任务结束后再运行，最多保留64次等待中的运行
+//! - `LimitBehavior::Drop`：放弃本次运行，并发送`SchedulerEvent::Skipped`事件
+//! - `LimitBehavior::Coalesce`：最多保留一次等待中的运行，其余的运行被放弃
+//!
+//! 等待中的运行不占用副reactor，由正在运行的任务结束后接着运行。
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn export_report() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let _ = scheduler.push_disorder_task_with_options(
+//!         "0/5 * * * * ?",
+//!         export_report,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new().max_concurrent(2, LimitBehavior::Coalesce),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 非cron计划
+//!
+//! `push_task_with_schedule`接收`Schedule`，不需要编写cron表达式：
+//!
+//! - `Schedule::Every`：固定频率，从新增任务时开始计算
+//! - `Schedule::FixedDelay`：固定延迟，上一次运行结束后再隔一段时间触发
+//! - `Schedule::After`：新增任务后隔一段时间触发一次
+//! - `Schedule::At`：在指定时间触发一次
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn sync_inventory() {}
+//!
+//! async fn warm_up() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let _ = scheduler.push_task_with_schedule(
+//!         Schedule::FixedDelay(Duration::from_secs(30)),
+//!         sync_inventory,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new(),
+//!     );
+//!     let _ = scheduler.push_task_with_schedule(
+//!         Schedule::After(Duration::from_secs(10)),
+//!         warm_up,
+//!         RepeatModel::Once,
+//!         TaskOptions::new(),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 生效时间与结束时间
+//!
+//! `TaskOptions::starting_at`设置任务的生效时间，`RepeatModel::Until`设置结束时间，任务只在两者之间触发。任务不再触发时（重复次数用尽、超出结束时间或一次性的计划已经触发）状态变为`TaskStatus::Completed`，发送`SchedulerEvent::Finished`事件并从调度器中移除：`task_info`、`list_tasks`等查询立即不再返回该任务，其TaskActor与名称在下一次新增、暂停、恢复或销毁任务时才释放
+//!
+//! ```rust,no_run
+//! use chrono::{Local, TimeZone};
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn promotion() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let starting_at = Local.with_ymd_and_hms(2030, 11, 1, 0, 0, 0).unwrap();
+//!     let until = Local.with_ymd_and_hms(2030, 11, 12, 0, 0, 0).unwrap();
+//!     let _ = scheduler.push_order_task_with_options(
+//!         "0 0/5 * * * ?",
+//!         promotion,
+//!         RepeatModel::Until(until),
+//!         TaskOptions::new().starting_at(starting_at),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 手动触发
+//!
+//! `trigger_now`立即运行一次任务，有序任务排在正在运行的任务之后，无序任务交给副reactor运行。手动触发不改变cron节奏、剩余运行次数与下一次触发时间，任务函数可以通过`TaskContext::is_manual`区分手动触发的运行
+//!
+//! ```rust,no_run
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn nightly_job() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     let task_id = scheduler
+//!         .push_task("0 0 2 * * ?", nightly_job, RepeatModel::Repetition)
+//!         .unwrap();
+//!     assert!(scheduler.trigger_now(task_id).await);
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 持久化
+//!
+//! 进程重启后通过`push_task`新增的任务会丢失。在`HandlerRegistry`中按名称注册任务函数，通过`push_registered_task`新增的任务可以通过`save`保存到`TaskStore`，重启后通过`restore`恢复，剩余运行次数、暂停状态与最近一次触发时间保持不变
+//!
+//! 开启`persistence` feature后可以使用基于JSON文件的`JsonFileStore`，也可以为数据库等其他存储实现`TaskStore`
+//!
+//! ```toml
+//! [dependencies]
+//! lynn_sundial = { version = "1", features = ["persistence"] }
+//! ```
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn cleanup_sessions() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let registry = HandlerRegistry::new().register("cleanup_sessions", cleanup_sessions);
+//!     let store = JsonFileStore::new("tasks.json");
+//!     let mut scheduler = Scheduler::new();
+//!     let restored = scheduler.restore(&store, &registry).await.unwrap();
+//!     if restored.is_empty() {
+//!         let _ = scheduler.push_registered_task(
+//!             "0 0/10 * * * ?".parse().unwrap(),
+//!             "cleanup_sessions",
+//!             &registry,
+//!             RepeatModel::Repetition,
+//!             TaskOptions::new().name("cleanup_sessions"),
+//!         );
+//!     }
+//!     // 定期保存快照
+//!     loop {
+//!         tokio::time::sleep(Duration::from_secs(60)).await;
+//!         let _ = scheduler.save(&store);
+//!     }
+//! }
+//! ```
+//!
+//! #### 错过触发
+//!
+//! 进程停止后通过`restore`恢复，或主reactor处理不及时，任务的实际触发时间晚于计划时间超过阈值时视为错过触发，发送`SchedulerEvent::Misfired`事件并按任务的`MisfirePolicy`处理：
+//!
+//! - `FireOnce`（默认）：立即运行一次，之后从当前时间开始计算下一次触发时间
+//! - `FireAll`：按计划时间依次补运行每一次错过的触发
+//! - `Skip`：不运行，从当前时间开始计算下一次触发时间
+//!
+//! 错过的触发都计入剩余运行次数。阈值默认为1秒，可以通过`SchedulerBuilder::misfire_threshold`设置默认值，通过`TaskOptions::misfire_threshold`为单个任务设置
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn report() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::builder()
+//!         .misfire_threshold(Duration::from_secs(5))
+//!         .build()
+//!         .unwrap();
+//!     let _ = scheduler.push_order_task_with_options(
+//!         "0 0 * * * ?",
+//!         report,
+//!         RepeatModel::Repetition,
+//!         TaskOptions::new()
+//!             .misfire_policy(MisfirePolicy::FireAll)
+//!             .misfire_threshold(Duration::from_secs(30)),
+//!     );
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 任务定义文件
+//!
+//! 开启`jobs` feature后，可以在TOML或JSON文件中描述任务，通过`load_jobs`按`handler`从`HandlerRegistry`中找到任务函数并新增任务，修改计划无需重新编译。扩展名为`json`时按JSON（`{"jobs": [...]}`）解析，其余按TOML解析
+//!
+//! ```toml
+//! [dependencies]
+//! lynn_sundial = { version = "1", features = ["jobs"] }
+//! ```
+//!
+//! ```toml
+//! [[jobs]]
+//! name = "cleanup_sessions"
+//! cron = "0 0/10 * * * ?"
+//! handler = "cleanup_sessions"
+//! order_type = "disorder"    # order（默认）或disorder
+//! repeat = 3                 # repetition（默认）、once或运行次数
+//! timezone = "Asia/Shanghai" # 未设置时使用调度器的默认时区
+//! tags = ["maintenance"]
+//! ```
+//!
+//! 文件中的任务全部检查通过后才会新增，名称重复、cron不合法、任务函数未注册或名称已经存在时不新增任何任务并返回`ERR_CODE(1008)`
+//!
+//! ```rust,ignore
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn cleanup_sessions() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let registry = HandlerRegistry::new().register("cleanup_sessions", cleanup_sessions);
+//!     let mut scheduler = Scheduler::new();
+//!     let task_ids = scheduler.load_jobs("jobs.toml", &registry).await.unwrap();
+//!     println!("loaded {} jobs", task_ids.len());
+//!     scheduler.wait_all().await
+//! }
+//! ```
+//!
+//! #### 重新加载任务定义文件
+//!
+//! 通过`reload_jobs`重新加载任务定义文件，或通过`watch_jobs`在后台监视文件，修改后再重新加载。重新加载时与上一次从该路径加载的任务比较：
+//!
+//! - 新增文件中新增的任务，销毁从文件中删除的任务
+//! - 只有cron变化的任务通过`update_cron_by_id`更新，运行次数、剩余运行次数与暂停状态保持不变
+//! - cron以外的字段变化的任务销毁后重新新增
+//!
+//! 文件不合法时不修改任何任务，并发送`SchedulerEvent::JobsReloadFailed`事件；成功时发送包含`JobsDiff`的`SchedulerEvent::JobsReloaded`事件
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! use lynn_sundial::schedule_api::*;
+//!
+//! async fn cleanup_sessions() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let registry = HandlerRegistry::new().register("cleanup_sessions", cleanup_sessions);
+//!     let mut scheduler = Scheduler::new();
+//!     let mut events = scheduler.subscribe();
+//!     tokio::spawn(async move {
+//!         while let Ok(event) = events.recv().await {
+//!             if let SchedulerEvent::JobsReloadFailed { path, error } = event {
+//!                 eprintln!("reload {} failed: {error}", path.display());
+//!             }
+//!         }
+//!     });
+//!     // 每5秒检查一次文件是否修改，监视期间调度器仍然可用，调度器停止后退出循环
+//!     let mut watcher = scheduler.watch_jobs("jobs.toml", Duration::from_secs(5));
+//!     while watcher.changed().await {
+//!         let _ = scheduler.reload_jobs(watcher.path(), &registry).await;
+//!     }
+//! }
+//! ```
+//!
+//! #### 命令行工具
+//!
+//! 开启`cli` feature后可以使用`sundial`命令行工具，校验与预览cron表达式，以及检查任务定义文件
+//!
+//! ```shell
+//! cargo install lynn_sundial --features cli
+//!
+//! # 校验cron表达式，不合法时说明原因（字段数量、不合法的字段及其取值范围）
+//! sundial validate "0/1 * * * * ?"
+//!
+//! # 按时区打印之后的10次触发时间
+//! sundial next "0 0 9 * * ?" -n 10 --tz Asia/Shanghai
+//!
+//! # 检查任务定义文件：格式、名称是否重复、cron是否合法以及是否还会触发
+//! sundial check jobs.toml
+//! ```
 
 /// 定时任务
 mod schedule;
@@ -0,0 +1,118 @@
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Local, TimeDelta};
+use tokio::sync::watch;
+
+/// 时钟休眠返回的future
+pub type ClockFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// ## 时钟
+/// 调度器通过时钟获取当前时间并驱动时间轮tick，默认使用系统时钟`SystemClock`
+///
+/// 测试时可以通过`Scheduler::with_clock`注入`TestClock`，手动推进时间
+pub trait Clock: Send + Sync + 'static {
+    /// 当前时间
+    fn now(&self) -> DateTime<Local>;
+
+    /// 主reactor每次tick前调用，休眠`duration`后返回
+    fn sleep(&self, duration: Duration) -> ClockFuture;
+}
+
+pub(crate) type SharedClock = Arc<dyn Clock>;
+
+/// ## 系统时钟
+/// 使用系统本地时间，通过`tokio::time::sleep`休眠
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// ## 手动推进的测试时钟
+/// 时间只会在调用`advance`时前进，主reactor在两次`advance`之间不会tick
+///
+/// 注意：
+/// - 一个`TestClock`只能注入一个`Scheduler`
+/// - `advance`在主reactor处理完推进的时间后返回，此时到期的任务已经分发，但任务函数可能仍在运行
+/// - 任务的超时时间与任务函数内部的`tokio::time`不受测试时钟影响
+#[derive(Clone)]
+pub struct TestClock {
+    inner: Arc<TestClockInner>,
+}
+
+struct TestClockInner {
+    /// 当前时间与推进的次数
+    now: watch::Sender<(DateTime<Local>, u64)>,
+    /// 主reactor已经看到的推进次数
+    observed: AtomicU64,
+    /// 主reactor已经处理完的推进次数
+    consumed: watch::Sender<u64>,
+}
+
+impl TestClock {
+    /// 以`start`作为初始时间创建测试时钟
+    pub fn new(start: DateTime<Local>) -> Self {
+        Self {
+            inner: Arc::new(TestClockInner {
+                now: watch::Sender::new((start, 0)),
+                observed: AtomicU64::new(0),
+                consumed: watch::Sender::new(0),
+            }),
+        }
+    }
+
+    /// 将时间推进`duration`，并等待主reactor处理完这段时间内到期的任务
+    pub async fn advance(&self, duration: Duration) {
+        let delta = TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX);
+        let mut generation = 0;
+        self.inner.now.send_modify(|(now, count)| {
+            *now = now.checked_add_signed(delta).unwrap_or(*now);
+            *count += 1;
+            generation = *count;
+        });
+        let mut consumed = self.inner.consumed.subscribe();
+        let _ = consumed.wait_for(|consumed| *consumed >= generation).await;
+    }
+}
+
+impl Debug for TestClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestClock")
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> DateTime<Local> {
+        self.inner.now.borrow().0
+    }
+
+    fn sleep(&self, _duration: Duration) -> ClockFuture {
+        let inner = self.inner.clone();
+        Box::pin(async move {
+            // 再次休眠说明上一次推进的时间已经处理完
+            let observed = inner.observed.load(Ordering::Acquire);
+            inner.consumed.send_replace(observed);
+            let mut now = inner.now.subscribe();
+            if let Ok(value) = now.wait_for(|(_, count)| *count > observed).await {
+                inner.observed.store(value.1, Ordering::Release);
+            }
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::schedule::{config::DEFAULT_MAX_WAITING_RUNS, task_actor::TaskRun};
+
+/// ## 达到并发上限时的处理方式
+/// 配合`TaskOptions::max_concurrent`使用
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum LimitBehavior {
+    /// 等待正在运行的任务结束后再运行，最多保留64次等待中的运行，超过时放弃并发送`SchedulerEvent::Skipped`事件
+    #[default]
+    Wait,
+    /// 放弃本次运行，并发送`SchedulerEvent::Skipped`事件
+    Drop,
+    /// 最多保留一次等待中的运行，其余的运行合并到该次运行中（放弃并发送`SchedulerEvent::Skipped`事件）
+    Coalesce,
+}
+
+/// 获取许可的结果
+pub(crate) enum Acquired {
+    /// 获取到许可，立即运行
+    Run(TaskRun, OwnedSemaphorePermit),
+    /// 放入等待队列，由持有许可的运行结束后接着运行
+    Waiting,
+    /// 放弃本次运行
+    Dropped(TaskRun),
+}
+
+/// ## 任务的并发上限
+/// 由同一任务的所有运行共享，每次运行前获取许可，运行结束后释放
+/// 等待中的运行不占用副reactor，放在任务自己的等待队列中，由结束的运行沿用许可接着运行
+#[derive(Clone)]
+pub(crate) struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    behavior: LimitBehavior,
+    /// 等待中的运行，获取许可失败与释放许可都在锁内进行，避免运行在许可释放后仍留在队列中
+    waiting: Arc<Mutex<VecDeque<TaskRun>>>,
+}
+
+impl ConcurrencyLimit {
+    pub(crate) fn new(max_concurrent: usize, behavior: LimitBehavior) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            behavior,
+            waiting: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// 获取一次运行的许可，无法获取时按`LimitBehavior`放入等待队列或放弃
+    pub(crate) fn acquire(&self, task_run: TaskRun) -> Acquired {
+        let Ok(mut waiting) = self.waiting.lock() else {
+            return Acquired::Dropped(task_run);
+        };
+        if let Ok(permit) = self.semaphore.clone().try_acquire_owned() {
+            return Acquired::Run(task_run, permit);
+        }
+        let max_waiting = match self.behavior {
+            LimitBehavior::Wait => DEFAULT_MAX_WAITING_RUNS,
+            LimitBehavior::Drop => 0,
+            LimitBehavior::Coalesce => 1,
+        };
+        if waiting.len() < max_waiting {
+            waiting.push_back(task_run);
+            Acquired::Waiting
+        } else {
+            Acquired::Dropped(task_run)
+        }
+    }
+
+    /// 一次运行结束后取出下一次等待中的运行并沿用许可，没有等待中的运行时释放许可
+    pub(crate) fn next_waiting(
+        &self,
+        permit: OwnedSemaphorePermit,
+    ) -> Option<(TaskRun, OwnedSemaphorePermit)> {
+        let mut waiting = self.waiting.lock().ok()?;
+        match waiting.pop_front() {
+            Some(task_run) => Some((task_run, permit)),
+            None => {
+                drop(permit);
+                None
+            }
+        }
+    }
+}
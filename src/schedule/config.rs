@@ -5,13 +5,21 @@ pub(crate) const DEFAULT_SECOND_TIME_WHEEL_SETTING: (usize, u64) = (60, 1000);
 /// 默认分钟时间轮配置，分60层，单位1min
 pub(crate) const DEFAULT_MINUTE_TIME_WHEEL_SETTING: (usize, u64) = (60, 60 * 1000);
 /// 默认小时时间轮配置，分24层，单位1小时
-pub(crate) const DEFAULT_HOUR_TIME_WHEEL_SETTING: (usize, u64) = (24, 1 * 60 * 60 * 1000);
+pub(crate) const DEFAULT_HOUR_TIME_WHEEL_SETTING: (usize, u64) = (24, 60 * 60 * 1000);
 /// 默认的tick间隔 25毫秒
 pub(crate) const DEFAULT_TICK_TIME: u64 = 25;
 /// 默认的副reactor线程数量（tokio线程，非真实thread）
 pub(crate) const DEFAULT_TASK_POOL_SIZE: usize = 32;
 /// 默认的channel大小
 pub(crate) const DEFAULT_CHANNEL_SIZE: usize = 8;
+/// 暂停中的任务检查是否恢复的间隔 1秒
+pub(crate) const DEFAULT_PAUSE_CHECK_TIME: i64 = 1000;
+/// 默认的事件channel大小
+pub(crate) const DEFAULT_EVENT_CHANNEL_SIZE: usize = 1024;
+/// 默认的错过触发阈值，任务触发晚于计划时间超过该值时视为错过触发 1秒
+pub(crate) const DEFAULT_MISFIRE_THRESHOLD: u64 = 1000;
+/// 达到并发上限时，`LimitBehavior::Wait`的任务最多保留的等待中的运行次数
+pub(crate) const DEFAULT_MAX_WAITING_RUNS: usize = 64;
 
 // Error
 /// 没有可以分配的taskid了
@@ -22,3 +30,102 @@ pub(crate) const DEFAULT_ERROR_CODE_1001: &str = "ERR_CODE(1001),Cron operation
 /// 更新cron到task出现未知原因错误
 pub(crate) const DEFAULT_ERROR_CODE_1002: &str =
     "ERR_CODE(1002),Updating cron failed for unknown reason";
+/// 任务函数所需的资源不存在
+pub(crate) const DEFAULT_ERROR_CODE_1003: &str =
+    "ERR_CODE(1003),Resource required by task handler not found";
+/// 调度器配置不合法
+pub(crate) const DEFAULT_ERROR_CODE_1004: &str = "ERR_CODE(1004),Invalid scheduler config";
+/// 调度器已经停止
+pub(crate) const DEFAULT_ERROR_CODE_1005: &str = "ERR_CODE(1005),Scheduler has been shut down";
+/// 任务名称已经存在
+pub(crate) const DEFAULT_ERROR_CODE_1006: &str = "ERR_CODE(1006),Task name already exists";
+/// 任务函数没有在注册表中注册
+pub(crate) const DEFAULT_ERROR_CODE_1007: &str = "ERR_CODE(1007),Task handler not registered";
+/// 任务定义文件不合法
+pub(crate) const DEFAULT_ERROR_CODE_1008: &str = "ERR_CODE(1008),Invalid job definition";
+
+/// ## 调度器配置
+/// 由`SchedulerBuilder`设置，未设置的项使用上面的默认值
+#[derive(Clone, Debug)]
+pub(crate) struct SchedulerConfig {
+    /// tick间隔（毫秒）
+    pub(crate) tick_time: u64,
+    /// 副reactor数量
+    pub(crate) task_pool_size: usize,
+    /// TaskActor的channel大小
+    pub(crate) channel_size: usize,
+    /// 事件channel大小
+    pub(crate) event_channel_size: usize,
+    /// 毫秒、秒、分钟、小时时间轮的配置，(槽位数量, 槽位间隔毫秒)
+    pub(crate) time_wheel_settings: [(usize, u64); 4],
+    /// 错过触发阈值（毫秒），未通过`TaskOptions::misfire_threshold`单独设置的任务使用
+    pub(crate) misfire_threshold: u64,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            tick_time: DEFAULT_TICK_TIME,
+            task_pool_size: DEFAULT_TASK_POOL_SIZE,
+            channel_size: DEFAULT_CHANNEL_SIZE,
+            event_channel_size: DEFAULT_EVENT_CHANNEL_SIZE,
+            time_wheel_settings: [
+                DEFAULT_MILLISECOND_TIME_WHEEL_SETTING,
+                DEFAULT_SECOND_TIME_WHEEL_SETTING,
+                DEFAULT_MINUTE_TIME_WHEEL_SETTING,
+                DEFAULT_HOUR_TIME_WHEEL_SETTING,
+            ],
+            misfire_threshold: DEFAULT_MISFIRE_THRESHOLD,
+        }
+    }
+}
+
+impl SchedulerConfig {
+    /// 检查配置是否合法，不合法时返回原因
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.tick_time == 0 {
+            return Err("tick time must be greater than 0".to_string());
+        }
+        if self.task_pool_size == 0 {
+            return Err("task pool size must be greater than 0".to_string());
+        }
+        if self.channel_size == 0 {
+            return Err("channel size must be greater than 0".to_string());
+        }
+        if self.event_channel_size == 0 {
+            return Err("event channel size must be greater than 0".to_string());
+        }
+        let names = ["millisecond", "second", "minute", "hour"];
+        for (index, (slot_len, interval)) in self.time_wheel_settings.iter().enumerate() {
+            let name = names[index];
+            if *slot_len == 0 || *interval == 0 {
+                return Err(format!(
+                    "{name} time wheel must have at least one slot and a non-zero interval"
+                ));
+            }
+            if index > 0 {
+                // 上一层需要覆盖本层的一个槽位间隔，降级时任务才能放入更低一层
+                let (lower_slot_len, lower_interval) = self.time_wheel_settings[index - 1];
+                if *interval <= lower_interval {
+                    return Err(format!(
+                        "{name} time wheel interval must be greater than the {} time wheel interval",
+                        names[index - 1]
+                    ));
+                }
+                if (lower_slot_len as u64).saturating_mul(lower_interval) < *interval {
+                    return Err(format!(
+                        "{} time wheel must cover at least one {name} time wheel slot",
+                        names[index - 1]
+                    ));
+                }
+            }
+        }
+        if self.tick_time > self.time_wheel_settings[0].1 {
+            return Err(
+                "tick time must not be greater than the millisecond time wheel interval"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::schedule::{
+    IntoSystem, Resources, SchedulerError, TaskContext,
+    task_actor::{ITaskHandler, TaskFuture},
+};
+
+type HandlerFactory =
+    Arc<dyn Fn(Resources) -> Result<Box<dyn ITaskHandler>, SchedulerError> + Send + Sync>;
+
+/// ## 任务函数注册表
+/// 在代码中按名称注册任务函数，恢复任务（`Scheduler::restore`）时按名称找到对应的任务函数
+///
+/// 同一名称重复注册时覆盖旧的任务函数
+#[derive(Clone, Default)]
+pub struct HandlerRegistry {
+    handlers: HashMap<String, HandlerFactory>,
+}
+
+impl HandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册任务函数，任务函数的形式与`push_task`相同
+    pub fn register<Marker>(
+        mut self,
+        name: impl Into<String>,
+        handle: impl IntoSystem<Marker> + Clone + Send + Sync + 'static,
+    ) -> Self {
+        let factory: HandlerFactory = Arc::new(move |resources| {
+            handle
+                .clone()
+                .to_system(resources)
+                .map(|system| Box::new(system) as Box<dyn ITaskHandler>)
+        });
+        self.handlers.insert(name.into(), factory);
+        self
+    }
+
+    /// 是否注册了该名称的任务函数
+    pub fn contains(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// 已注册的名称，按名称升序排列
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.handlers.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<RegisteredHandler> {
+        self.handlers
+            .get(name)
+            .map(|factory| RegisteredHandler(factory.clone()))
+    }
+}
+
+/// 注册表中的任务函数，新增任务时构建
+pub(crate) struct RegisteredHandler(HandlerFactory);
+
+impl IntoSystem<RegisteredHandler> for RegisteredHandler {
+    type System = Box<dyn ITaskHandler>;
+
+    fn to_system(self, resources: Resources) -> Result<Self::System, SchedulerError> {
+        (self.0)(resources)
+    }
+}
+
+impl ITaskHandler for Box<dyn ITaskHandler> {
+    fn run(&self, context: TaskContext) -> TaskFuture {
+        self.as_ref().run(context)
+    }
+
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+}
@@ -0,0 +1,194 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::schedule::{
+    HandlerRegistry, RepeatModel, Schedule, TaskOptions, TaskOrderType, TaskTimeZone,
+};
+
+/// ## 任务定义
+/// 在TOML或JSON文件中描述任务，通过`Scheduler::load_jobs`按`handler`从`HandlerRegistry`中找到任务函数并新增任务
+///
+/// TOML文件中每个任务为一个`[[jobs]]`，JSON文件为`{"jobs": [...]}`，字段相同：
+/// ```toml
+/// [[jobs]]
+/// name = "cleanup_sessions"
+/// cron = "0 0/10 * * * ?"
+/// handler = "cleanup_sessions"
+/// order_type = "disorder"    # order（默认）或disorder
+/// repeat = 3                 # repetition（默认）、once或运行次数
+/// timezone = "Asia/Shanghai" # 未设置时使用调度器的默认时区
+/// tags = ["maintenance"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobDefinition {
+    /// 任务名称，同一文件中不能重复
+    pub name: String,
+    pub cron: String,
+    /// 任务函数在`HandlerRegistry`中的名称
+    pub handler: String,
+    #[serde(default = "default_order_type")]
+    pub order_type: TaskOrderType,
+    #[serde(
+        default = "default_repeat",
+        deserialize_with = "job_repeat::deserialize"
+    )]
+    pub repeat: RepeatModel,
+    #[serde(default)]
+    pub timezone: Option<TaskTimeZone>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// ## 重新加载任务定义文件的结果
+/// 通过`Scheduler::reload_jobs`获取，各项均为任务id，按文件中的顺序排列
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JobsDiff {
+    /// 文件中新增的任务
+    pub added: Vec<usize>,
+    /// 只有cron变化的任务，通过`update_cron_by_id`更新，运行次数与暂停状态保持不变
+    pub updated: Vec<usize>,
+    /// cron以外的字段变化或已经完成的任务，销毁后重新新增，为新的任务id
+    pub recreated: Vec<usize>,
+    /// 从文件中删除而销毁的任务
+    pub removed: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobFile {
+    #[serde(default)]
+    jobs: Vec<JobDefinition>,
+}
+
+fn default_order_type() -> TaskOrderType {
+    TaskOrderType::Order
+}
+
+fn default_repeat() -> RepeatModel {
+    RepeatModel::Repetition
+}
+
+impl JobDefinition {
+    /// 读取任务定义文件，扩展名为`json`时按JSON解析，其余按TOML解析
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<JobDefinition>, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let job_file: JobFile = if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            serde_json::from_str(&content)?
+        } else {
+            toml::from_str(&content)?
+        };
+        Ok(job_file.jobs)
+    }
+
+    /// 解析cron表达式
+    pub fn schedule(&self) -> Result<Schedule, cron::error::Error> {
+        self.cron.parse()
+    }
+
+    /// 检查任务定义，返回发现的所有问题，没有问题时返回空列表
+    /// - 名称为空或重复
+    /// - cron表达式不合法
+    /// - 传入注册表时，任务函数没有注册
+    pub fn check(jobs: &[JobDefinition], registry: Option<&HandlerRegistry>) -> Vec<String> {
+        let mut problems = vec![];
+        let mut names = HashSet::new();
+        for job in jobs {
+            if job.name.is_empty() {
+                problems.push(format!("job with cron `{}` has an empty name", job.cron));
+            } else if !names.insert(job.name.as_str()) {
+                problems.push(format!("{}: duplicate job name", job.name));
+            }
+            if let Err(error) = job.schedule() {
+                problems.push(format!(
+                    "{}: invalid cron `{}`: {error}",
+                    job.name, job.cron
+                ));
+            }
+            if let Some(registry) = registry
+                && !registry.contains(&job.handler)
+            {
+                problems.push(format!(
+                    "{}: handler `{}` is not registered",
+                    job.name, job.handler
+                ));
+            }
+        }
+        problems
+    }
+
+    /// 与`other`相比是否只有cron不同
+    pub(crate) fn only_cron_differs(&self, other: &JobDefinition) -> bool {
+        self.cron != other.cron
+            && JobDefinition {
+                cron: other.cron.clone(),
+                ..self.clone()
+            } == *other
+    }
+
+    /// 新增任务时的选项，任务函数名称由`Scheduler::load_jobs`设置
+    pub(crate) fn options(&self) -> TaskOptions {
+        let options = TaskOptions::new()
+            .name(self.name.clone())
+            .tags(self.tags.iter().cloned());
+        match self.timezone {
+            Some(time_zone) => options.time_zone(time_zone),
+            None => options,
+        }
+    }
+}
+
+/// `repeat`可以是`"repetition"`、`"once"`或大于0的运行次数
+mod job_repeat {
+    use std::fmt;
+
+    use serde::{
+        Deserializer,
+        de::{Error, Unexpected, Visitor},
+    };
+
+    use crate::schedule::RepeatModel;
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RepeatModel, D::Error> {
+        deserializer.deserialize_any(RepeatVisitor)
+    }
+
+    struct RepeatVisitor;
+
+    impl Visitor<'_> for RepeatVisitor {
+        type Value = RepeatModel;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("\"repetition\", \"once\" or a number of runs greater than 0")
+        }
+
+        fn visit_u64<E: Error>(self, times: u64) -> Result<Self::Value, E> {
+            match usize::try_from(times) {
+                Ok(times) if times > 0 => Ok(RepeatModel::Times(times)),
+                _ => Err(E::invalid_value(Unexpected::Unsigned(times), &self)),
+            }
+        }
+
+        fn visit_i64<E: Error>(self, times: i64) -> Result<Self::Value, E> {
+            match u64::try_from(times) {
+                Ok(times) => self.visit_u64(times),
+                Err(_) => Err(E::invalid_value(Unexpected::Signed(times), &self)),
+            }
+        }
+
+        fn visit_str<E: Error>(self, repeat: &str) -> Result<Self::Value, E> {
+            match repeat {
+                "repetition" => Ok(RepeatModel::Repetition),
+                "once" => Ok(RepeatModel::Once),
+                _ => Err(E::invalid_value(Unexpected::Str(repeat), &self)),
+            }
+        }
+    }
+}
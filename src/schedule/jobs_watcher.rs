@@ -0,0 +1,56 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use tokio::sync::mpsc::{self, Receiver};
+
+use crate::schedule::shutdown::SharedLifecycle;
+
+/// ## 任务定义文件的监视句柄
+/// 通过`Scheduler::watch_jobs`获取，在后台每隔一段时间检查文件的修改时间，不占用调度器
+///
+/// 文件变化后由调用方通过`Scheduler::reload_jobs`重新加载，多次修改在下一次`changed`之前合并为一次
+pub struct JobsWatcher {
+    path: PathBuf,
+    changed: Receiver<()>,
+}
+
+impl JobsWatcher {
+    pub(crate) fn spawn(path: PathBuf, interval: Duration, lifecycle: &SharedLifecycle) -> Self {
+        let (sender, changed) = mpsc::channel(1);
+        let mut shutdown = lifecycle.subscribe();
+        let watched_path = path.clone();
+        lifecycle.spawn(async move {
+            // 文件的修改时间属于真实时间，不使用调度器的时钟
+            let mut last_modified = None;
+            loop {
+                let modified = tokio::fs::metadata(&watched_path)
+                    .await
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                if last_modified != Some(modified) {
+                    last_modified = Some(modified);
+                    let _ = sender.try_send(());
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = sender.closed() => break,
+                    _ = shutdown.wait_for(|shutdown| *shutdown) => break,
+                }
+            }
+        });
+        Self { path, changed }
+    }
+
+    /// 被监视的文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// #### 等待文件变化
+    /// 第一次调用立即返回，之后在文件的修改时间变化时返回`true`；调度器停止后返回`false`
+    pub async fn changed(&mut self) -> bool {
+        self.changed.recv().await.is_some()
+    }
+}
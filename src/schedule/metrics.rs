@@ -0,0 +1,272 @@
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::schedule::SchedulerEvent;
+
+/// 触发延迟直方图的桶（秒）
+const FIRE_LATENESS_BUCKETS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 60.0,
+];
+/// 运行时长直方图的桶（秒）
+const RUN_DURATION_BUCKETS: [f64; 14] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 60.0, 300.0,
+];
+
+/// ## 调度器指标
+/// 由事件发送端在发送事件时记录，无需订阅事件
+pub(crate) struct Metrics {
+    fires_total: AtomicU64,
+    completions_total: AtomicU64,
+    failures_total: AtomicU64,
+    misfires_total: AtomicU64,
+    fire_lateness: Histogram,
+    run_duration: Histogram,
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        Self {
+            fires_total: AtomicU64::new(0),
+            completions_total: AtomicU64::new(0),
+            failures_total: AtomicU64::new(0),
+            misfires_total: AtomicU64::new(0),
+            fire_lateness: Histogram::new(&FIRE_LATENESS_BUCKETS),
+            run_duration: Histogram::new(&RUN_DURATION_BUCKETS),
+        }
+    }
+
+    pub(crate) fn record(&self, event: &SchedulerEvent) {
+        match event {
+            SchedulerEvent::Fired {
+                scheduled, actual, ..
+            } => {
+                self.fires_total.fetch_add(1, Ordering::Relaxed);
+                // 允许提前少量时间触发，提前时记为0
+                let lateness = actual
+                    .signed_duration_since(*scheduled)
+                    .to_std()
+                    .unwrap_or_default();
+                self.fire_lateness.observe(lateness.as_secs_f64());
+            }
+            SchedulerEvent::Completed { duration, .. } => {
+                self.completions_total.fetch_add(1, Ordering::Relaxed);
+                self.run_duration.observe(duration.as_secs_f64());
+            }
+            SchedulerEvent::Failed { duration, .. } => {
+                self.failures_total.fetch_add(1, Ordering::Relaxed);
+                self.run_duration.observe(duration.as_secs_f64());
+            }
+            SchedulerEvent::Cancelled { duration, .. } => {
+                self.run_duration.observe(duration.as_secs_f64());
+            }
+            SchedulerEvent::Misfired { .. } => {
+                self.misfires_total.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn snapshot(
+        &self,
+        wheel_occupancy: WheelOccupancy,
+        worker_queue_depth: usize,
+    ) -> MetricsSnapshot {
+        MetricsSnapshot {
+            fires_total: self.fires_total.load(Ordering::Relaxed),
+            completions_total: self.completions_total.load(Ordering::Relaxed),
+            failures_total: self.failures_total.load(Ordering::Relaxed),
+            misfires_total: self.misfires_total.load(Ordering::Relaxed),
+            fire_lateness: self.fire_lateness.snapshot(),
+            run_duration: self.run_duration.snapshot(),
+            wheel_occupancy,
+            worker_queue_depth,
+        }
+    }
+}
+
+/// 固定桶的直方图，耗时以微秒累加
+struct Histogram {
+    bounds: &'static [f64],
+    /// 每个桶（非累计）的数量，最后一个为`+Inf`
+    counts: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, seconds: f64) {
+        let index = self
+            .bounds
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.counts[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add((seconds * 1_000_000.0) as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let mut cumulative = 0;
+        let mut buckets = Vec::with_capacity(self.bounds.len());
+        for (index, bound) in self.bounds.iter().enumerate() {
+            cumulative += self.counts[index].load(Ordering::Relaxed);
+            buckets.push((*bound, cumulative));
+        }
+        cumulative += self.counts[self.bounds.len()].load(Ordering::Relaxed);
+        HistogramSnapshot {
+            buckets,
+            count: cumulative,
+            sum: self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+        }
+    }
+}
+
+/// ## 直方图快照
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistogramSnapshot {
+    /// `(上界秒数, 不超过该上界的累计数量)`，按上界升序排列，不包含`+Inf`
+    pub buckets: Vec<(f64, u64)>,
+    /// 总数量
+    pub count: u64,
+    /// 总秒数
+    pub sum: f64,
+}
+
+/// ## 各层时间轮中的任务数量
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WheelOccupancy {
+    pub millisecond: usize,
+    pub second: usize,
+    pub minute: usize,
+    pub hour: usize,
+    /// 超出小时时间轮范围的溢出队列
+    pub overflow: usize,
+}
+
+/// ## 指标快照
+/// 通过`Scheduler::metrics_snapshot`获取，`render_prometheus`渲染为Prometheus文本格式
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// 触发次数，包含重试
+    pub fires_total: u64,
+    /// 运行成功次数
+    pub completions_total: u64,
+    /// 运行失败次数，包含超时
+    pub failures_total: u64,
+    /// 错过触发次数
+    pub misfires_total: u64,
+    /// 实际触发时间晚于计划时间的秒数
+    pub fire_lateness: HistogramSnapshot,
+    /// 运行时长的秒数，包含被取消的运行
+    pub run_duration: HistogramSnapshot,
+    /// 各层时间轮中的任务数量
+    pub wheel_occupancy: WheelOccupancy,
+    /// 等待副reactor运行的无序任务数量
+    pub worker_queue_depth: usize,
+}
+
+impl MetricsSnapshot {
+    /// 渲染为Prometheus文本格式（text/plain; version=0.0.4），指标名以`lynn_sundial_`开头
+    pub fn render_prometheus(&self) -> String {
+        let mut output = String::new();
+        for (name, help, value) in [
+            (
+                "fires_total",
+                "Total number of task fires.",
+                self.fires_total,
+            ),
+            (
+                "completions_total",
+                "Total number of successful task runs.",
+                self.completions_total,
+            ),
+            (
+                "failures_total",
+                "Total number of failed or timed out task runs.",
+                self.failures_total,
+            ),
+            (
+                "misfires_total",
+                "Total number of fires later than the misfire threshold.",
+                self.misfires_total,
+            ),
+        ] {
+            write_header(&mut output, name, help, "counter");
+            let _ = writeln!(output, "lynn_sundial_{name} {value}");
+        }
+        write_histogram(
+            &mut output,
+            "fire_lateness_seconds",
+            "Delay between the scheduled and the actual fire time.",
+            &self.fire_lateness,
+        );
+        write_histogram(
+            &mut output,
+            "run_duration_seconds",
+            "Duration of task runs, including cancelled runs.",
+            &self.run_duration,
+        );
+        write_header(
+            &mut output,
+            "wheel_tasks",
+            "Number of tasks in each time wheel layer.",
+            "gauge",
+        );
+        let occupancy = &self.wheel_occupancy;
+        for (layer, value) in [
+            ("millisecond", occupancy.millisecond),
+            ("second", occupancy.second),
+            ("minute", occupancy.minute),
+            ("hour", occupancy.hour),
+            ("overflow", occupancy.overflow),
+        ] {
+            let _ = writeln!(
+                output,
+                "lynn_sundial_wheel_tasks{{layer=\"{layer}\"}} {value}"
+            );
+        }
+        write_header(
+            &mut output,
+            "worker_queue_depth",
+            "Number of task runs waiting for a worker.",
+            "gauge",
+        );
+        let _ = writeln!(
+            output,
+            "lynn_sundial_worker_queue_depth {}",
+            self.worker_queue_depth
+        );
+        output
+    }
+}
+
+fn write_header(output: &mut String, name: &str, help: &str, metric_type: &str) {
+    let _ = writeln!(output, "# HELP lynn_sundial_{name} {help}");
+    let _ = writeln!(output, "# TYPE lynn_sundial_{name} {metric_type}");
+}
+
+fn write_histogram(output: &mut String, name: &str, help: &str, histogram: &HistogramSnapshot) {
+    write_header(output, name, help, "histogram");
+    for (bound, count) in &histogram.buckets {
+        let _ = writeln!(
+            output,
+            "lynn_sundial_{name}_bucket{{le=\"{bound}\"}} {count}"
+        );
+    }
+    let _ = writeln!(
+        output,
+        "lynn_sundial_{name}_bucket{{le=\"+Inf\"}} {}",
+        histogram.count
+    );
+    let _ = writeln!(output, "lynn_sundial_{name}_sum {}", histogram.sum);
+    let _ = writeln!(output, "lynn_sundial_{name}_count {}", histogram.count);
+}
@@ -0,0 +1,15 @@
+/// ## 错过触发的处理方式
+/// 进程停止期间或主reactor落后时，任务的实际触发时间晚于计划时间超过阈值即视为错过触发，通过`TaskOptions::misfire_policy`设置
+///
+/// 阈值通过`TaskOptions::misfire_threshold`或`SchedulerBuilder::misfire_threshold`设置，默认为1秒
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum MisfirePolicy {
+    /// 立即运行一次，之后从当前时间开始计算下一次触发时间，其余错过的触发时间不再运行
+    #[default]
+    FireOnce,
+    /// 按计划时间依次补运行每一次错过的触发，计入剩余运行次数
+    FireAll,
+    /// 不运行，从当前时间开始计算下一次触发时间，错过的触发计入剩余运行次数
+    Skip,
+}
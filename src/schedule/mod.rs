@@ -1,22 +1,88 @@
+mod clock;
+mod concurrency_limit;
 mod config;
+mod handler_registry;
+#[cfg(feature = "jobs")]
+mod job_definition;
+#[cfg(feature = "jobs")]
+mod jobs_watcher;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod misfire_policy;
+mod overlap_policy;
 mod reactor;
+mod retry_policy;
+mod scheduler_builder;
+mod scheduler_event;
+mod shutdown;
+mod system_param;
 mod task_actor;
+mod task_context;
+mod task_info;
 mod task_manager;
+mod task_options;
+mod task_record;
+mod task_schedule;
+mod task_stats;
+mod task_store;
 mod time_wheel;
+mod time_zone;
 
+pub use clock::{Clock, ClockFuture, SystemClock, TestClock};
+pub use concurrency_limit::LimitBehavior;
+pub use handler_registry::HandlerRegistry;
+#[cfg(feature = "jobs")]
+pub use job_definition::{JobDefinition, JobsDiff};
+#[cfg(feature = "jobs")]
+pub use jobs_watcher::JobsWatcher;
+#[cfg(feature = "metrics")]
+pub use metrics::{HistogramSnapshot, MetricsSnapshot, WheelOccupancy};
+pub use misfire_policy::MisfirePolicy;
+pub use overlap_policy::OverlapPolicy;
+pub use retry_policy::{Backoff, RetryPolicy};
+pub use scheduler_builder::SchedulerBuilder;
+pub use scheduler_event::SchedulerEvent;
+pub use shutdown::{ShutdownHandle, ShutdownMode};
+pub use system_param::{Res, Resources, SystemParam, TaskId};
+pub use task_actor::{TaskOrderType, TaskStatus};
+pub use task_context::TaskContext;
+pub use task_info::TaskInfo;
+pub use task_options::TaskOptions;
+pub use task_schedule::Schedule;
+pub use task_stats::{IntoTaskResult, TaskRunResult, TaskStats};
+#[cfg(feature = "persistence")]
+pub use task_store::JsonFileStore;
+pub use task_store::{TaskSnapshot, TaskStore};
+pub use time_zone::TaskTimeZone;
+
+use crate::schedule::clock::SharedClock;
+#[cfg(feature = "jobs")]
+use crate::schedule::config::DEFAULT_ERROR_CODE_1008;
 use crate::schedule::config::{
     DEFAULT_ERROR_CODE_1000, DEFAULT_ERROR_CODE_1001, DEFAULT_ERROR_CODE_1002,
+    DEFAULT_ERROR_CODE_1003, DEFAULT_ERROR_CODE_1005, DEFAULT_ERROR_CODE_1006,
+    DEFAULT_ERROR_CODE_1007, SchedulerConfig,
 };
-use crate::schedule::task_actor::{ITaskHandler, Task, TaskOrderType, TaskStatus};
+use crate::schedule::scheduler_event::EventSender;
+use crate::schedule::shutdown::{Lifecycle, SharedLifecycle};
+use crate::schedule::task_actor::{ITaskHandler, Task, TaskFuture, TaskSignal};
 #[cfg(feature = "schedule")]
 use crate::schedule::task_manager::TaskManager;
-use crate::schedule::{reactor::TaskReactor, time_wheel::TierTimeWheel};
-use chrono::Local;
+use crate::schedule::{reactor::TaskReactor, task_record::TaskRecord, time_wheel::TierTimeWheel};
+use chrono::{DateTime, Local};
+#[cfg(feature = "jobs")]
+use std::collections::HashMap;
 use std::error::Error;
+#[cfg(feature = "jobs")]
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::{marker::PhantomData, pin::Pin, str::FromStr};
+use std::time::Duration;
+use std::{marker::PhantomData, str::FromStr};
+use tokio::sync::mpsc::channel;
 
 #[cfg(feature = "schedule")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
 pub enum RepeatModel {
     /// 只运行一次
     Once,
@@ -24,6 +90,27 @@ pub enum RepeatModel {
     Repetition,
     /// 运行指定次数
     Times(usize),
+    /// 重复运行直到指定时间，晚于该时间的触发时间不再触发
+    Until(DateTime<Local>),
+}
+
+impl RepeatModel {
+    /// 剩余运行次数，`None`表示不限
+    pub(crate) fn remaining(&self) -> Option<usize> {
+        match self {
+            RepeatModel::Once => Some(1),
+            RepeatModel::Repetition | RepeatModel::Until(_) => None,
+            RepeatModel::Times(times) => Some(*times),
+        }
+    }
+
+    /// 结束时间，只有`Until`有结束时间
+    pub(crate) fn until(&self) -> Option<DateTime<Local>> {
+        match self {
+            RepeatModel::Until(until) => Some(*until),
+            _ => None,
+        }
+    }
 }
 
 /// ## 定时任务调度器
@@ -32,36 +119,136 @@ pub struct Scheduler {
     pub(crate) time_wheel: Arc<TierTimeWheel>,
     pub(crate) task_reactor: TaskReactor,
     pub(crate) task_manager: TaskManager,
+    pub(crate) resources: Resources,
+    pub(crate) default_timeout: Option<Duration>,
+    pub(crate) default_time_zone: TaskTimeZone,
+    pub(crate) config: SchedulerConfig,
+    pub(crate) events: EventSender,
+    pub(crate) lifecycle: SharedLifecycle,
+    /// 从任务定义文件新增的任务，重新加载时与文件的内容比较
+    #[cfg(feature = "jobs")]
+    pub(crate) loaded_jobs: HashMap<PathBuf, Vec<(JobDefinition, usize)>>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Scheduler {
     pub fn new() -> Self {
-        let time_wheel = Arc::new(TierTimeWheel::new());
+        Self::with_clock(SystemClock)
+    }
+
+    /// #### 使用指定的时钟创建调度器
+    /// 调度器的当前时间、时间轮tick以及下一次运行时间的计算都使用该时钟，测试时可以注入`TestClock`手动推进时间
+    pub fn with_clock(clock: impl Clock) -> Self {
+        Self::from_config(SchedulerConfig::default(), Arc::new(clock))
+    }
+
+    /// #### 通过构建器创建调度器
+    /// 可以设置tick间隔、副reactor数量、channel大小以及各层时间轮的槽位数量与间隔
+    pub fn builder() -> SchedulerBuilder {
+        SchedulerBuilder::new()
+    }
+
+    pub(crate) fn from_config(config: SchedulerConfig, clock: SharedClock) -> Self {
+        let events = EventSender::new(config.event_channel_size);
+        let lifecycle = Lifecycle::new_shared();
+        let time_wheel =
+            TierTimeWheel::new_shared(clock, events.clone(), lifecycle.clone(), &config);
         let mut task_reactor = TaskReactor::new();
-        let task_manager = TaskManager::new();
-        task_reactor.start(time_wheel.clone(), task_manager.get_notice_list());
+        let task_manager = TaskManager::new(events.clone());
+        task_reactor.start(time_wheel.clone(), task_manager.get_notice_list(), &config);
 
         Self {
             time_wheel,
             task_reactor,
             task_manager,
+            resources: Resources::default(),
+            default_timeout: None,
+            default_time_zone: TaskTimeZone::Local,
+            config,
+            events,
+            lifecycle,
+            #[cfg(feature = "jobs")]
+            loaded_jobs: HashMap::new(),
         }
     }
 
+    /// #### 获取指标快照
+    /// 包含触发、成功、失败、错过触发的次数，触发延迟与运行时长的直方图，各层时间轮中的任务数量以及等待运行的无序任务数量
+    ///
+    /// 需要开启`metrics` feature，通过`MetricsSnapshot::render_prometheus`渲染为Prometheus文本格式
+    #[cfg(feature = "metrics")]
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.events.get_metrics().snapshot(
+            self.time_wheel.get_occupancy(),
+            self.task_reactor.get_queue_depth(),
+        )
+    }
+
+    /// #### 订阅调度器事件
+    /// 返回的接收端只能收到订阅之后发生的事件，包括任务的新增、触发、运行结果以及状态变更
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<SchedulerEvent> {
+        self.events.subscribe()
+    }
+
+    /// #### 设置默认的运行超时时间
+    /// 对之后新增的、未通过`TaskOptions::timeout`单独设置超时时间的任务生效，`None`表示不限制
+    pub fn set_default_timeout(&mut self, timeout: Option<Duration>) {
+        self.default_timeout = timeout;
+    }
+
+    /// #### 设置默认时区
+    /// 对之后新增的、未通过`TaskOptions::time_zone`单独设置时区的任务生效，默认为系统本地时区
+    pub fn set_default_time_zone(&mut self, time_zone: impl Into<TaskTimeZone>) {
+        self.default_time_zone = time_zone.into();
+    }
+
+    /// #### 插入共享资源
+    /// 任务函数可以通过`Res<R>`参数获取，同一类型的资源重复插入时会覆盖旧值
+    /// 注意：需要在新增使用该资源的任务之前插入，否则新增任务时会返回错误
+    pub fn insert_resource<R: Send + Sync + 'static>(&mut self, resource: R) {
+        self.resources.insert(resource);
+    }
+
     /// #### 异步阻塞等待定时器
     /// 注意：定时器的内部reactor在new时已经启动，`wait_all`方法是用于阻塞主线程而额外提供的异步方法，你也可以在主线程使用类似`loop{}`来避免主线程提前结束（不推荐）
-    pub async fn wait_all(&mut self) {
-        let _ = self.task_reactor.wait_all().await;
+    ///
+    /// 调度器通过`shutdown`或`ShutdownHandle::shutdown`停止完成后返回
+    pub async fn wait_all(&self) {
+        self.lifecycle.wait_terminated().await;
+    }
+
+    /// #### 停止调度器
+    /// - `ShutdownMode::Drain { deadline }`：不再触发新的任务，等待已经触发的任务运行结束，超过`deadline`后中止剩余的任务
+    /// - `ShutdownMode::Immediate`：立即中止所有正在运行的任务
+    ///
+    /// 返回时主reactor、副reactor与所有TaskActor都已经退出，`wait_all`随之返回，之后新增任务会返回错误
+    pub async fn shutdown(&self, mode: ShutdownMode) {
+        self.lifecycle.shutdown(mode).await;
+    }
+
+    /// #### 获取停止句柄
+    /// 用于在其他异步任务中停止调度器，同时主线程通过`wait_all`等待停止完成
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.lifecycle.handle()
     }
 
     /// #### 新增默认的有序定时任务
     /// - 使用`push_task`来新增等同于`push_order_task`的有序定时任务
     /// - 使用`push_order_task`来新增上一次任务A尚未结束时，下一次需要运行的任务A进行排队等候的定时任务
     /// - 使用`push_disorder_task`来新增无需关注上一次任务A是否结束，就允许新任务A运行的定时任务
-    pub fn push_task(
+    ///
+    /// 任务函数可以是`async fn()`，也可以接收最多8个实现了`SystemParam`的参数，如`async fn(Res<R>, TaskId)`
+    ///
+    /// 任务函数可以返回`()`或`Result<(), E>`，返回`Err`时会记录到该任务的运行统计中，通过`task_stats`查询
+    pub fn push_task<Marker>(
         &mut self,
         cron: &str,
-        handle: impl IntoSystem,
+        handle: impl IntoSystem<Marker>,
         repeat: RepeatModel,
     ) -> Result<usize, Box<dyn std::error::Error>> {
         self.push_order_task(cron, handle, repeat)
@@ -70,77 +257,466 @@ impl Scheduler {
     /// #### 新增有序定时任务
     /// 注意：
     /// - 所有为`RepeatModel::Repetition/::Times(>0)`的同一定时任务A，上一次任务A尚未结束时，下一次需要运行的任务A进行排队等候的定时任务
-    pub fn push_order_task(
+    pub fn push_order_task<Marker>(
         &mut self,
         cron: &str,
-        handle: impl IntoSystem,
+        handle: impl IntoSystem<Marker>,
         repeat: RepeatModel,
     ) -> Result<usize, Box<dyn std::error::Error>> {
-        if let Some(task_id) = self.task_manager.get_new_id() {
-            let cron_schedule = cron::Schedule::from_str(cron)?;
-            if let Some(next_time) = cron_schedule.upcoming(Local).next() {
-                let now_time = Local::now();
-                let time_delta = next_time.signed_duration_since(now_time);
-                let milliseconds = time_delta.num_milliseconds();
-                let task = Task::new(
-                    cron_schedule,
-                    Arc::new(Box::new(handle.to_system())),
-                    repeat,
-                    next_time,
-                    TaskOrderType::Order,
-                    task_id,
-                );
-                self.task_manager
-                    .insert_new_task(task_id, task.get_sender());
-                self.time_wheel.push_T_to_time_wheel(task, milliseconds);
-                return Ok(task_id);
+        self.push_order_task_with_options(cron, handle, repeat, TaskOptions::default())
+    }
+
+    /// #### 新增带选项的有序定时任务
+    /// 通过`TaskOptions`设置失败重试策略、运行超时时间、时区等
+    pub fn push_order_task_with_options<Marker>(
+        &mut self,
+        cron: &str,
+        handle: impl IntoSystem<Marker>,
+        repeat: RepeatModel,
+        options: TaskOptions,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        self.push_task_with_order_type(cron, handle, repeat, TaskOrderType::Order, options)
+    }
+
+    /// #### 新增无序定时任务
+    /// 注意：
+    /// - 所有为`RepeatModel::Repetition/::Times(>0)`的同一定时任务，无需关注上一次任务是否结束就允许运行新的任务（如：任务A，无需关注上一次任务A是否结束，就允许新任务A运行的定时任务）
+    pub fn push_disorder_task<Marker>(
+        &mut self,
+        cron: &str,
+        handle: impl IntoSystem<Marker>,
+        repeat: RepeatModel,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        self.push_disorder_task_with_options(cron, handle, repeat, TaskOptions::default())
+    }
+
+    /// #### 新增带选项的无序定时任务
+    /// 通过`TaskOptions`设置失败重试策略、运行超时时间、时区等
+    pub fn push_disorder_task_with_options<Marker>(
+        &mut self,
+        cron: &str,
+        handle: impl IntoSystem<Marker>,
+        repeat: RepeatModel,
+        options: TaskOptions,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        self.push_task_with_order_type(cron, handle, repeat, TaskOrderType::Disorder, options)
+    }
+
+    /// #### 新增按`Schedule`触发的定时任务
+    /// 支持cron表达式、固定频率、固定延迟、延迟一次以及指定时间一次的触发计划
+    ///
+    /// 默认为有序任务，通过`TaskOptions::overlap_policy`设置为无序等其他重叠策略
+    pub fn push_task_with_schedule<Marker>(
+        &mut self,
+        schedule: Schedule,
+        handle: impl IntoSystem<Marker>,
+        repeat: RepeatModel,
+        options: TaskOptions,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        self.push_schedule_with_order_type(
+            schedule,
+            handle,
+            repeat,
+            TaskOrderType::Order,
+            options,
+            None,
+            TaskStatus::Running,
+        )
+    }
+
+    /// #### 新增使用注册表中任务函数的定时任务
+    /// 与`push_task_with_schedule`相同，任务函数按名称从`HandlerRegistry`中获取，名称未注册时返回错误
+    ///
+    /// 只有通过该方法新增的任务才能通过`snapshot`/`save`保存，并在进程重启后通过`restore`恢复
+    pub fn push_registered_task(
+        &mut self,
+        schedule: Schedule,
+        handler: &str,
+        registry: &HandlerRegistry,
+        repeat: RepeatModel,
+        options: TaskOptions,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        self.push_registered_task_at(
+            schedule,
+            handler,
+            registry,
+            repeat,
+            TaskOrderType::Order,
+            options,
+            None,
+            TaskStatus::Running,
+        )
+    }
+
+    /// #### 从任务定义文件新增任务
+    /// 扩展名为`json`时按JSON解析，其余按TOML解析，字段见`JobDefinition`
+    ///
+    /// 任务函数按`handler`从`HandlerRegistry`中获取，新增的任务与`push_registered_task`相同，可以通过`save`保存
+    ///
+    /// 返回新增的任务id，顺序与文件中的任务相同。文件不合法、任务函数未注册或名称已经存在时不新增任何任务并返回错误
+    ///
+    /// 之后可以通过`reload_jobs`重新加载同一路径的文件，或通过`watch_jobs`监视文件的修改
+    #[cfg(feature = "jobs")]
+    pub async fn load_jobs(
+        &mut self,
+        path: impl AsRef<Path>,
+        registry: &HandlerRegistry,
+    ) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let jobs = JobDefinition::load(path)?;
+        let mut problems = JobDefinition::check(&jobs, Some(registry));
+        for job in &jobs {
+            if self.task_manager.get_task_id_by_name(&job.name).is_some() {
+                problems.push(format!("{}: task name already exists", job.name));
             }
+        }
+        if !problems.is_empty() {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1008}: {}",
+                problems.join("; ")
+            ))));
+        }
+        let mut task_ids = Vec::with_capacity(jobs.len());
+        for job in &jobs {
+            let pushed = self.push_registered_task_at(
+                job.schedule()?,
+                &job.handler,
+                registry,
+                job.repeat.clone(),
+                job.order_type,
+                job.options(),
+                None,
+                TaskStatus::Running,
+            );
+            match pushed {
+                Ok(task_id) => task_ids.push(task_id),
+                Err(error) => {
+                    // 已经新增的任务全部销毁，保持全部新增或全部不新增
+                    for task_id in task_ids {
+                        self.destory_task_by_id(task_id).await;
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        self.loaded_jobs.insert(
+            path.to_path_buf(),
+            jobs.into_iter().zip(task_ids.iter().copied()).collect(),
+        );
+        Ok(task_ids)
+    }
+
+    /// #### 重新加载任务定义文件
+    /// 与上一次从该路径加载的任务比较：
+    /// - 新增文件中新增的任务
+    /// - 销毁从文件中删除的任务
+    /// - 只有cron变化的任务通过`update_cron_by_id`更新，运行次数与暂停状态保持不变
+    /// - cron以外的字段变化或已经完成的任务销毁后重新新增
+    /// - 没有变化的任务保持不变，已经完成的任务不会重新新增
+    ///
+    /// 没有通过`load_jobs`加载过的文件，其中的任务全部新增。文件不合法、任务函数未注册或名称被其他任务使用时不修改任何任务，
+    /// 发送`SchedulerEvent::JobsReloadFailed`事件并返回错误；成功时发送`SchedulerEvent::JobsReloaded`事件
+    #[cfg(feature = "jobs")]
+    pub async fn reload_jobs(
+        &mut self,
+        path: impl AsRef<Path>,
+        registry: &HandlerRegistry,
+    ) -> Result<JobsDiff, Box<dyn std::error::Error>> {
+        let path = path.as_ref().to_path_buf();
+        match self.apply_jobs(&path, registry).await {
+            Ok(diff) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!(path = %path.display(), ?diff, "jobs reloaded");
+                self.events.send(SchedulerEvent::JobsReloaded {
+                    path,
+                    diff: diff.clone(),
+                });
+                Ok(diff)
+            }
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(path = %path.display(), %error, "jobs reload failed");
+                self.events.send(SchedulerEvent::JobsReloadFailed {
+                    path,
+                    error: error.to_string(),
+                });
+                Err(error)
+            }
+        }
+    }
+
+    #[cfg(feature = "jobs")]
+    async fn apply_jobs(
+        &mut self,
+        path: &Path,
+        registry: &HandlerRegistry,
+    ) -> Result<JobsDiff, Box<dyn std::error::Error>> {
+        if self.lifecycle.is_shutdown() {
             return Err(Box::new(SchedulerError(
-                DEFAULT_ERROR_CODE_1001.to_string(),
-            )));
-        } else {
-            return Err(Box::new(SchedulerError(
-                DEFAULT_ERROR_CODE_1000.to_string(),
+                DEFAULT_ERROR_CODE_1005.to_string(),
             )));
         }
+        let jobs = JobDefinition::load(path)?;
+        let previous: HashMap<String, (JobDefinition, usize)> = self
+            .loaded_jobs
+            .get(path)
+            .into_iter()
+            .flatten()
+            .map(|(job, task_id)| (job.name.clone(), (job.clone(), *task_id)))
+            .collect();
+        let mut problems = JobDefinition::check(&jobs, Some(registry));
+        let now = self.time_wheel.now();
+        for job in &jobs {
+            let previous_job = previous.get(&job.name);
+            if previous_job.is_none() && self.task_manager.get_task_id_by_name(&job.name).is_some()
+            {
+                problems.push(format!("{}: task name already exists", job.name));
+            }
+            // 新增或cron变化的任务需要有下一次触发时间
+            if previous_job.is_none_or(|(previous_job, _)| previous_job != job)
+                && let Ok(schedule) = job.schedule()
+                && schedule
+                    .first_after(&job.timezone.unwrap_or(self.default_time_zone), &now)
+                    .is_none()
+            {
+                problems.push(format!(
+                    "{}: cron `{}` never fires again",
+                    job.name, job.cron
+                ));
+            }
+        }
+        if !problems.is_empty() {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1008}: {}",
+                problems.join("; ")
+            ))));
+        }
+
+        let mut diff = JobsDiff::default();
+        for (name, (_, task_id)) in &previous {
+            if !jobs.iter().any(|job| job.name == *name) {
+                self.destory_task_by_id(*task_id).await;
+                diff.removed.push(*task_id);
+            }
+        }
+        diff.removed.sort();
+        let mut loaded = Vec::with_capacity(jobs.len());
+        let mut failures = vec![];
+        for job in jobs {
+            let mut task_id = None;
+            let mut is_new = true;
+            if let Some((previous_job, previous_id)) = previous.get(&job.name) {
+                is_new = false;
+                if previous_job == &job {
+                    task_id = Some(*previous_id);
+                } else if previous_job.only_cron_differs(&job)
+                    && let Ok(Schedule::Cron(cron_schedule)) = job.schedule()
+                    && self
+                        .task_manager
+                        .update_cron_by_id(*previous_id, *cron_schedule)
+                        .await
+                {
+                    diff.updated.push(*previous_id);
+                    task_id = Some(*previous_id);
+                } else {
+                    self.destory_task_by_id(*previous_id).await;
+                }
+            }
+            if task_id.is_none() {
+                let pushed = job.schedule().map_err(Into::into).and_then(|schedule| {
+                    self.push_registered_task_at(
+                        schedule,
+                        &job.handler,
+                        registry,
+                        job.repeat.clone(),
+                        job.order_type,
+                        job.options(),
+                        None,
+                        TaskStatus::Running,
+                    )
+                });
+                match pushed {
+                    Ok(new_id) => {
+                        if is_new {
+                            diff.added.push(new_id);
+                        } else {
+                            diff.recreated.push(new_id);
+                        }
+                        task_id = Some(new_id);
+                    }
+                    // 检查之后仍然失败（如任务id用尽）的任务不再记录，下一次重新加载时作为新的任务
+                    Err(error) => failures.push(format!("{}: {error}", job.name)),
+                }
+            }
+            if let Some(task_id) = task_id {
+                loaded.push((job, task_id));
+            }
+        }
+        self.loaded_jobs.insert(path.to_path_buf(), loaded);
+        if !failures.is_empty() {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1008}: {}",
+                failures.join("; ")
+            ))));
+        }
+        Ok(diff)
     }
 
-    /// #### 新增无序定时任务
-    /// 注意：
-    /// - 所有为`RepeatModel::Repetition/::Times(>0)`的同一定时任务，无需关注上一次任务是否结束就允许运行新的任务（如：任务A，无需关注上一次任务A是否结束，就允许新任务A运行的定时任务）
-    pub fn push_disorder_task(
+    /// #### 监视任务定义文件
+    /// 在后台每隔`interval`检查文件的修改时间，返回的`JobsWatcher`在第一次以及文件变化时就绪，
+    /// 之后通过`reload_jobs`重新加载，重新加载的结果通过`SchedulerEvent::JobsReloaded/JobsReloadFailed`事件报告
+    ///
+    /// 监视期间调度器仍然可用，调度器停止或`JobsWatcher`被丢弃后停止监视
+    #[cfg(feature = "jobs")]
+    pub fn watch_jobs(&self, path: impl AsRef<Path>, interval: Duration) -> JobsWatcher {
+        JobsWatcher::spawn(path.as_ref().to_path_buf(), interval, &self.lifecycle)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_registered_task_at(
+        &mut self,
+        schedule: Schedule,
+        handler: &str,
+        registry: &HandlerRegistry,
+        repeat: RepeatModel,
+        task_order_type: TaskOrderType,
+        mut options: TaskOptions,
+        first_fire: Option<DateTime<Local>>,
+        status: TaskStatus,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let Some(handle) = registry.get(handler) else {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1007}: {handler}"
+            ))));
+        };
+        options.handler = Some(handler.to_string());
+        self.push_schedule_with_order_type(
+            schedule,
+            handle,
+            repeat,
+            task_order_type,
+            options,
+            first_fire,
+            status,
+        )
+    }
+
+    fn push_task_with_order_type<Marker>(
         &mut self,
         cron: &str,
-        handle: impl IntoSystem,
+        handle: impl IntoSystem<Marker>,
+        repeat: RepeatModel,
+        task_order_type: TaskOrderType,
+        options: TaskOptions,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let schedule = Schedule::from_str(cron)?;
+        self.push_schedule_with_order_type(
+            schedule,
+            handle,
+            repeat,
+            task_order_type,
+            options,
+            None,
+            TaskStatus::Running,
+        )
+    }
+
+    /// `first_fire`为恢复任务时保存的下一次触发时间，为`None`时按计划计算
+    ///
+    /// `status`为`TaskStatus::Pause`时任务直接以暂停状态新增，恢复前不会运行
+    #[allow(clippy::too_many_arguments)]
+    fn push_schedule_with_order_type<Marker>(
+        &mut self,
+        schedule: Schedule,
+        handle: impl IntoSystem<Marker>,
         repeat: RepeatModel,
+        task_order_type: TaskOrderType,
+        mut options: TaskOptions,
+        first_fire: Option<DateTime<Local>>,
+        status: TaskStatus,
     ) -> Result<usize, Box<dyn std::error::Error>> {
+        let overlap_policy = *options
+            .overlap_policy
+            .get_or_insert(OverlapPolicy::from(task_order_type));
+        let task_order_type = overlap_policy.order_type();
+        if self.lifecycle.is_shutdown() {
+            return Err(Box::new(SchedulerError(
+                DEFAULT_ERROR_CODE_1005.to_string(),
+            )));
+        }
+        if let Some(name) = options.name.as_deref()
+            && self.task_manager.get_task_id_by_name(name).is_some()
+        {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1006}: {name}"
+            ))));
+        }
+        options.timeout = options.timeout.or(self.default_timeout);
+        let time_zone = *options.time_zone.get_or_insert(self.default_time_zone);
+        let system = handle.to_system(self.resources.clone())?;
         if let Some(task_id) = self.task_manager.get_new_id() {
-            let cron_schedule = cron::Schedule::from_str(cron)?;
-            if let Some(next_time) = cron_schedule.upcoming(Local).next() {
-                let now_time = Local::now();
+            let now_time = self.time_wheel.now();
+            let after = options
+                .starting_at
+                .map_or(now_time, |starting_at| now_time.max(starting_at));
+            if let Some(next_time) = first_fire
+                .or_else(|| schedule.first_after(&time_zone, &after))
+                .filter(|next_time| repeat.until().is_none_or(|until| *next_time <= until))
+            {
                 let time_delta = next_time.signed_duration_since(now_time);
                 let milliseconds = time_delta.num_milliseconds();
-                let task = Task::new(
-                    cron_schedule,
-                    Arc::new(Box::new(handle.to_system())),
+                let name = options.name.clone();
+                let record = TaskRecord::new_shared(
+                    options.handler.as_deref().unwrap_or(system.name()),
+                    schedule.clone(),
+                    &options,
+                    &repeat,
+                    next_time,
+                );
+                let (task, task_actor) = Task::new(
+                    schedule,
+                    Arc::new(Box::new(system)),
                     repeat,
                     next_time,
-                    TaskOrderType::Disorder,
+                    task_order_type,
+                    task_id,
+                    record.clone(),
+                    options,
+                    &self.time_wheel,
+                    self.config.channel_size,
+                    status.clone(),
+                );
+                // 暂停中的任务不启动TaskActor，时间轮只定期检查是否恢复
+                let idle_actor = match status {
+                    TaskStatus::Pause => {
+                        if let Ok(mut record) = record.lock() {
+                            record.status = TaskStatus::Pause;
+                        }
+                        Some(task_actor)
+                    }
+                    _ => {
+                        task_actor.start_actor();
+                        None
+                    }
+                };
+                self.task_manager.insert_new_task(
                     task_id,
+                    name,
+                    task.get_sender(),
+                    record,
+                    idle_actor,
                 );
-                self.task_manager
-                    .insert_new_task(task_id, task.get_sender());
                 self.time_wheel.push_T_to_time_wheel(task, milliseconds);
-                return Ok(task_id);
+                Ok(task_id)
             } else {
-                return Err(Box::new(SchedulerError(
+                Err(Box::new(SchedulerError(
                     DEFAULT_ERROR_CODE_1001.to_string(),
-                )));
+                )))
             }
         } else {
-            return Err(Box::new(SchedulerError(
+            Err(Box::new(SchedulerError(
                 DEFAULT_ERROR_CODE_1000.to_string(),
-            )));
+            )))
         }
     }
 
@@ -179,53 +755,296 @@ impl Scheduler {
         }
         Ok(())
     }
+
+    /// #### 立即运行一次任务
+    /// 有序任务在TaskActor中排在正在运行的任务之后，无序任务直接放入副reactor的全局队列
+    ///
+    /// 手动触发不改变任务的cron节奏、剩余运行次数与下一次触发时间，也不受重叠策略限制，但受并发上限限制
+    /// 任务不存在、暂停中、已完成或调度器已经停止时返回`false`
+    pub async fn trigger_now(&mut self, task_id: usize) -> bool {
+        if self.lifecycle.is_shutdown() {
+            return false;
+        }
+        let Some((sender, task_order_type)) = self.task_manager.get_trigger_target(task_id) else {
+            return false;
+        };
+        let now = self.time_wheel.now();
+        let context = TaskContext::manual(task_id, now);
+        match task_order_type {
+            TaskOrderType::Order => {
+                self.send_triggered_event(task_id, now);
+                sender.send(TaskSignal::RunHandle(context)).await.is_ok()
+            }
+            TaskOrderType::Disorder => {
+                let (tx, mut rx) = channel(1);
+                let _ = sender.send(TaskSignal::GetTaskRun(context, tx)).await;
+                let Some(task_run) = rx.recv().await else {
+                    return false;
+                };
+                self.send_triggered_event(task_id, now);
+                self.task_reactor.push_task_run(task_run);
+                true
+            }
+        }
+    }
+
+    fn send_triggered_event(&self, task_id: usize, actual: DateTime<Local>) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(task_id, "task triggered manually");
+        self.events
+            .send(SchedulerEvent::Triggered { task_id, actual });
+    }
+
+    /// #### 按名称查询任务id
+    /// 只能查询通过`TaskOptions::name`设置了名称的任务
+    pub fn task_id_by_name(&self, name: &str) -> Option<usize> {
+        self.task_manager.get_task_id_by_name(name)
+    }
+
+    /// #### 暂停带有指定标签的所有任务
+    /// 返回暂停成功的任务id，按task_id升序排列
+    pub async fn pause_by_tag(&mut self, tag: &str) -> Vec<usize> {
+        self.task_manager
+            .update_task_status_by_tag(tag, TaskStatus::Pause)
+            .await
+    }
+
+    /// #### 恢复带有指定标签的所有任务
+    /// 返回恢复成功的任务id，按task_id升序排列
+    pub async fn restart_by_tag(&mut self, tag: &str) -> Vec<usize> {
+        self.task_manager
+            .update_task_status_by_tag(tag, TaskStatus::Running)
+            .await
+    }
+
+    /// #### 销毁带有指定标签的所有任务
+    /// 返回销毁成功的任务id，按task_id升序排列
+    pub async fn destory_by_tag(&mut self, tag: &str) -> Vec<usize> {
+        self.task_manager
+            .update_task_status_by_tag(tag, TaskStatus::Destory)
+            .await
+    }
+
+    /// #### 更新带有指定标签的所有任务的cron
+    /// 返回更新成功的任务id，按task_id升序排列，cron不合法时不更新任何任务并返回错误
+    pub async fn update_cron_by_tag(
+        &mut self,
+        tag: &str,
+        cron: &str,
+    ) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+        let cron_schedule = cron::Schedule::from_str(cron)?;
+        let mut task_ids = vec![];
+        for task_id in self.task_manager.get_task_ids_by_tag(tag) {
+            if self
+                .task_manager
+                .update_cron_by_id(task_id, cron_schedule.clone())
+                .await
+            {
+                task_ids.push(task_id);
+            }
+        }
+        Ok(task_ids)
+    }
+
+    /// #### 查询任务运行统计
+    /// 包含运行/成功/失败次数以及最近一次的错误信息，任务不存在、已销毁或已完成时返回`None`
+    pub fn task_stats(&self, task_id: usize) -> Option<TaskStats> {
+        self.task_manager.get_task_stats(task_id)
+    }
+
+    /// #### 查询所有任务
+    /// 返回运行中与暂停中的任务信息，按task_id升序排列
+    pub fn list_tasks(&self) -> Vec<TaskInfo> {
+        self.task_manager.list_tasks()
+    }
+
+    /// #### 查询任务信息
+    /// 包含名称、cron、状态、剩余运行次数、下一次/最近一次触发时间以及最近一次运行结果，任务不存在、已销毁或已完成时返回`None`
+    pub fn task_info(&self, task_id: usize) -> Option<TaskInfo> {
+        self.task_manager.get_task_info(task_id)
+    }
+
+    /// #### 查询之后的触发时间
+    /// 按任务的cron与时区计算之后最多`n`次触发时间，不超过剩余运行次数，任务不存在、已销毁或已完成时返回`None`
+    pub fn upcoming(&self, task_id: usize, n: usize) -> Option<Vec<DateTime<Local>>> {
+        self.task_manager
+            .get_upcoming(task_id, n, self.time_wheel.now())
+    }
+
+    /// #### 获取任务快照
+    /// 只包含通过`push_registered_task`新增的运行中与暂停中的任务，按task_id升序排列
+    pub fn snapshot(&self) -> Vec<TaskSnapshot> {
+        self.task_manager.get_snapshots()
+    }
+
+    /// #### 保存任务快照
+    /// 将`snapshot`的结果保存到`store`，覆盖之前保存的快照，可以定期或在停止调度器前调用
+    pub fn save(&self, store: &impl TaskStore) -> Result<(), Box<dyn std::error::Error>> {
+        store.save(&self.snapshot())
+    }
+
+    /// #### 恢复任务
+    /// 从`store`读取最近一次保存的快照，按名称从`registry`获取任务函数后重新新增任务，返回新的task_id
+    ///
+    /// - 剩余运行次数、暂停状态与最近一次触发时间保持不变
+    /// - 按保存的下一次触发时间触发，已经错过时按任务的`MisfirePolicy`运行一次、补运行或跳过
+    /// - 任一任务函数未注册、名称已经存在或不会再触发时不恢复任何任务并返回错误
+    pub async fn restore(
+        &mut self,
+        store: &impl TaskStore,
+        registry: &HandlerRegistry,
+    ) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+        let snapshots = store.load()?;
+        if let Some(snapshot) = snapshots
+            .iter()
+            .find(|snapshot| !registry.contains(&snapshot.handler))
+        {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1007}: {}",
+                snapshot.handler
+            ))));
+        }
+        let mut task_ids = Vec::with_capacity(snapshots.len());
+        for snapshot in snapshots {
+            let options = TaskOptions {
+                retry_policy: snapshot.retry_policy,
+                timeout: snapshot.timeout,
+                time_zone: Some(snapshot.time_zone),
+                name: snapshot.name,
+                tags: snapshot.tags,
+                overlap_policy: Some(snapshot.overlap_policy),
+                max_concurrent: snapshot.max_concurrent,
+                starting_at: snapshot.starting_at,
+                misfire_policy: Some(snapshot.misfire_policy),
+                misfire_threshold: snapshot.misfire_threshold,
+                handler: None,
+            };
+            let pushed = self.push_registered_task_at(
+                snapshot.schedule,
+                &snapshot.handler,
+                registry,
+                snapshot.repeat,
+                TaskOrderType::Order,
+                options,
+                snapshot.next_fire,
+                snapshot.status,
+            );
+            let task_id = match pushed {
+                Ok(task_id) => task_id,
+                Err(error) => {
+                    // 已经恢复的任务全部销毁，保持全部恢复或全部不恢复
+                    for task_id in task_ids {
+                        self.destory_task_by_id(task_id).await;
+                    }
+                    return Err(error);
+                }
+            };
+            self.task_manager.set_last_fire(task_id, snapshot.last_fire);
+            task_ids.push(task_id);
+        }
+        Ok(task_ids)
+    }
+
+    /// #### 查询最近一次运行失败的任务
+    /// 返回`(task_id, TaskStats)`列表，按task_id升序排列
+    pub fn failing_tasks(&self) -> Vec<(usize, TaskStats)> {
+        self.task_manager.get_failing_tasks()
+    }
+}
+
+/// 调度器释放时中止所有内部异步任务，需要等待正在运行的任务时请先调用`shutdown`
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        self.lifecycle.abort();
+    }
 }
 
-pub(crate) trait IntoSystem: Sized {
+pub(crate) trait IntoSystem<Marker>: Sized {
     type System: ITaskHandler + 'static;
-    fn to_system(self) -> Self::System;
+    fn to_system(self, resources: Resources) -> Result<Self::System, SchedulerError>;
 }
 
-impl<F: SystemParamFunction> IntoSystem for F {
-    type System = FunctionSystem<F>;
+impl<Marker: 'static, F: SystemParamFunction<Marker>> IntoSystem<Marker> for F {
+    type System = FunctionSystem<Marker, F>;
 
-    fn to_system(self) -> Self::System {
-        FunctionSystem {
+    fn to_system(self, resources: Resources) -> Result<Self::System, SchedulerError> {
+        if let Err(type_name) = F::validate(&resources) {
+            return Err(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1003}: {type_name}"
+            )));
+        }
+        Ok(FunctionSystem {
             func: self,
+            resources,
             _maker: PhantomData,
-        }
+        })
     }
 }
 
-#[derive(Clone)]
-pub(crate) struct FunctionSystem<F>
+pub(crate) struct FunctionSystem<Marker, F>
 where
-    F: SystemParamFunction + 'static,
+    F: SystemParamFunction<Marker> + 'static,
 {
     func: F,
-    _maker: PhantomData<()>,
+    resources: Resources,
+    _maker: PhantomData<fn() -> Marker>,
 }
 
-impl<F: SystemParamFunction> ITaskHandler for FunctionSystem<F> {
-    fn run(&self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
-        Box::pin(self.func.run())
+impl<Marker: 'static, F: SystemParamFunction<Marker>> ITaskHandler for FunctionSystem<Marker, F> {
+    fn run(&self, context: TaskContext) -> TaskFuture {
+        self.func.run(context, &self.resources)
+    }
+
+    fn name(&self) -> &'static str {
+        std::any::type_name::<F>()
     }
 }
 
-pub(crate) trait SystemParamFunction: Send + Sync + 'static {
-    fn run(&self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+/// `Marker`用于区分不同参数形式的任务函数，由编译器自动推导
+pub(crate) trait SystemParamFunction<Marker>: Send + Sync + 'static {
+    fn validate(resources: &Resources) -> Result<(), String>;
+    fn run(&self, context: TaskContext, resources: &Resources) -> TaskFuture;
 }
 
-impl<T, Fut> SystemParamFunction for T
-where
-    T: Fn() -> Fut + Send + Sync + 'static,
-    Fut: Future<Output = ()> + Send + 'static,
-{
-    fn run(&self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
-        Box::pin(self())
-    }
+macro_rules! impl_system_param_function {
+    ($($param:ident),*) => {
+        impl<T, Fut, $($param: SystemParam),*> SystemParamFunction<fn($($param,)*) -> Fut> for T
+        where
+            T: Fn($($param),*) -> Fut + Send + Sync + 'static,
+            Fut: Future + Send + 'static,
+            Fut::Output: IntoTaskResult,
+        {
+            fn validate(resources: &Resources) -> Result<(), String> {
+                $($param::validate(resources)?;)*
+                Ok(())
+            }
+
+            fn run(&self, context: TaskContext, resources: &Resources) -> TaskFuture {
+                $(
+                    let Some($param) = $param::fetch(&context, resources) else {
+                        let type_name = std::any::type_name::<$param>();
+                        return Box::pin(async move {
+                            Err(format!("{DEFAULT_ERROR_CODE_1003}: {type_name}"))
+                        });
+                    };
+                )*
+                let future = self($($param),*);
+                Box::pin(async move { future.await.into_task_result() })
+            }
+        }
+    };
 }
 
+impl_system_param_function!();
+impl_system_param_function!(P1);
+impl_system_param_function!(P1, P2);
+impl_system_param_function!(P1, P2, P3);
+impl_system_param_function!(P1, P2, P3, P4);
+impl_system_param_function!(P1, P2, P3, P4, P5);
+impl_system_param_function!(P1, P2, P3, P4, P5, P6);
+impl_system_param_function!(P1, P2, P3, P4, P5, P6, P7);
+impl_system_param_function!(P1, P2, P3, P4, P5, P6, P7, P8);
+
 #[derive(Debug)]
 struct SchedulerError(String);
 
@@ -0,0 +1,40 @@
+use crate::schedule::task_actor::TaskOrderType;
+
+/// ## 重叠策略
+/// 任务触发时上一次运行尚未结束的处理方式，通过`TaskOptions::overlap_policy`设置
+///
+/// 未设置时`push_order_task`为`Queue`，`push_disorder_task`为`Parallel`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverlapPolicy {
+    /// 排队等待上一次运行结束，即有序任务
+    #[default]
+    Queue,
+    /// 不关注上一次运行，直接并行运行，即无序任务
+    Parallel,
+    /// 上一次触发的运行尚未结束（包括排队中）时跳过本次触发，并发送`SchedulerEvent::Skipped`事件
+    Skip,
+    /// 取消已触发尚未结束的运行（包括排队中）后运行本次触发，被取消的运行记为`TaskRunResult::Cancelled`
+    Replace,
+}
+
+impl OverlapPolicy {
+    /// 只有`Queue`在TaskActor内运行，其余策略都由副reactor运行
+    pub(crate) fn order_type(&self) -> TaskOrderType {
+        match self {
+            OverlapPolicy::Queue => TaskOrderType::Order,
+            OverlapPolicy::Parallel | OverlapPolicy::Skip | OverlapPolicy::Replace => {
+                TaskOrderType::Disorder
+            }
+        }
+    }
+}
+
+impl From<TaskOrderType> for OverlapPolicy {
+    fn from(task_order_type: TaskOrderType) -> Self {
+        match task_order_type {
+            TaskOrderType::Order => OverlapPolicy::Queue,
+            TaskOrderType::Disorder => OverlapPolicy::Parallel,
+        }
+    }
+}
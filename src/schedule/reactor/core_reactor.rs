@@ -1,48 +1,47 @@
-use std::{sync::Arc, thread::sleep, time::Duration};
+use std::{sync::Arc, time::Duration};
 
-use chrono::Local;
 use crossbeam_deque::Injector;
-use tokio::{sync::RwLock, task::JoinHandle};
 
-use crate::schedule::{
-    config::DEFAULT_TICK_TIME,
-    task_actor::{ITaskHandler, TaskStatus},
-    time_wheel::TierTimeWheel,
-};
+use crate::schedule::{task_actor::TaskRun, task_manager::NoticeList, time_wheel::TierTimeWheel};
 
-pub(super) struct CoreReactor {
-    pub(super) core_join_handle: Option<JoinHandle<()>>,
-}
+pub(super) struct CoreReactor;
 
 impl CoreReactor {
     pub(crate) fn new() -> Self {
-        Self {
-            core_join_handle: None,
-        }
+        Self
     }
 
     pub(crate) fn start(
         &mut self,
         time_wheel: Arc<TierTimeWheel>,
-        global_queue: Arc<Injector<Arc<Box<dyn ITaskHandler>>>>,
-        notice_list: Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>>,
+        global_queue: Arc<Injector<TaskRun>>,
+        notice_list: NoticeList,
+        tick_time: u64,
     ) {
-        let core_join_handle = tokio::spawn(async move {
+        let clock = time_wheel.get_clock();
+        let lifecycle = time_wheel.get_lifecycle();
+        let mut shutdown = lifecycle.subscribe();
+        let mut last_tick_time = clock.now();
+        lifecycle.clone().spawn(async move {
             let time_wheel = time_wheel;
-            let mut tick_detal = DEFAULT_TICK_TIME;
             loop {
-                let start_time = Local::now();
-                sleep(Duration::from_millis(DEFAULT_TICK_TIME));
-                let handle_vec = time_wheel.tick(tick_detal, notice_list.clone()).await;
-                for handle in handle_vec {
-                    global_queue.push(handle);
+                tokio::select! {
+                    _ = clock.sleep(Duration::from_millis(tick_time)) => {}
+                    _ = shutdown.wait_for(|shutdown| *shutdown) => break,
+                }
+                let now_time = clock.now();
+                let tick_detal = now_time
+                    .signed_duration_since(last_tick_time)
+                    .num_milliseconds()
+                    .max(0) as u64;
+                last_tick_time = now_time;
+                let task_runs = time_wheel.tick(tick_detal, notice_list.clone()).await;
+                for task_run in task_runs {
+                    global_queue.push(task_run);
                 }
-                let end_time = Local::now();
-                tick_detal = end_time
-                    .signed_duration_since(start_time)
-                    .num_milliseconds() as u64;
             }
+            // 停止后不再tick，已经分发的任务由副reactor运行完
+            lifecycle.stop_dispatch();
         });
-        self.core_join_handle = Some(core_join_handle);
     }
 }
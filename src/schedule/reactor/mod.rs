@@ -1,10 +1,10 @@
 use std::sync::Arc;
 
-use tokio::sync::RwLock;
-
 use crate::schedule::{
+    config::SchedulerConfig,
     reactor::{core_reactor::CoreReactor, task_reactor::TasksManager},
-    task_actor::TaskStatus,
+    task_actor::TaskRun,
+    task_manager::NoticeList,
     time_wheel::TierTimeWheel,
 };
 
@@ -25,25 +25,31 @@ impl TaskReactor {
         }
     }
 
-    pub(crate) async fn wait_all(&mut self) -> Result<(), tokio::task::JoinError> {
-        if let Some(handle) = self.core_reactor.core_join_handle.as_mut() {
-            //handle.
-            handle.await
-        } else {
-            Ok(())
-        }
+    /// 将无序任务的运行放入全局队列
+    pub(crate) fn push_task_run(&self, task_run: TaskRun) {
+        self.task_manager.push(task_run);
+    }
+
+    pub(crate) fn get_queue_depth(&self) -> usize {
+        self.task_manager.get_queue_depth()
     }
 
     pub(crate) fn start(
         &mut self,
         time_wheel: Arc<TierTimeWheel>,
-        notice_list: Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>>,
+        notice_list: NoticeList,
+        config: &SchedulerConfig,
     ) {
-        self.task_manager.start();
+        self.task_manager.start(
+            config.task_pool_size,
+            config.tick_time,
+            time_wheel.get_lifecycle(),
+        );
         self.core_reactor.start(
             time_wheel,
             self.task_manager.get_global_queue(),
             notice_list,
+            config.tick_time,
         );
     }
 }
@@ -2,13 +2,11 @@ use std::{sync::Arc, time::Duration};
 
 use crossbeam_deque::{Injector, Steal, Stealer, Worker};
 
-use crate::schedule::{
-    config::{DEFAULT_TASK_POOL_SIZE, DEFAULT_TICK_TIME},
-    task_actor::ITaskHandler,
-};
+use crate::schedule::{shutdown::SharedLifecycle, task_actor::TaskRun};
 
 pub(super) struct TasksManager {
-    global_queue: Arc<Injector<Arc<Box<dyn ITaskHandler>>>>,
+    global_queue: Arc<Injector<TaskRun>>,
+    stealers: Option<Arc<Vec<Stealer<TaskRun>>>>,
 }
 
 impl TasksManager {
@@ -16,35 +14,57 @@ impl TasksManager {
         let sender = Arc::new(Injector::new());
         Self {
             global_queue: sender,
+            stealers: None,
         }
     }
 
-    pub(crate) fn get_global_queue(&self) -> Arc<Injector<Arc<Box<dyn ITaskHandler>>>> {
+    /// 全局队列与各副reactor本地队列中等待运行的任务数量
+    pub(crate) fn get_queue_depth(&self) -> usize {
+        let local_depth = self
+            .stealers
+            .as_ref()
+            .map_or(0, |stealers| stealers.iter().map(Stealer::len).sum());
+        self.global_queue.len() + local_depth
+    }
+
+    pub(crate) fn push(&self, task_run: TaskRun) {
+        self.global_queue.push(task_run);
+    }
+
+    pub(crate) fn get_global_queue(&self) -> Arc<Injector<TaskRun>> {
         self.global_queue.clone()
     }
 
-    pub(crate) fn start(&self) {
-        let mut local_queues: Vec<Worker<Arc<Box<dyn ITaskHandler>>>> =
-            Vec::with_capacity(DEFAULT_TASK_POOL_SIZE);
-        let mut stealers: Vec<Stealer<Arc<Box<dyn ITaskHandler>>>> =
-            Vec::with_capacity(DEFAULT_TASK_POOL_SIZE);
-        for _ in 0..DEFAULT_TASK_POOL_SIZE {
+    pub(crate) fn start(
+        &mut self,
+        task_pool_size: usize,
+        tick_time: u64,
+        lifecycle: SharedLifecycle,
+    ) {
+        let mut local_queues: Vec<Worker<TaskRun>> = Vec::with_capacity(task_pool_size);
+        let mut stealers: Vec<Stealer<TaskRun>> = Vec::with_capacity(task_pool_size);
+        for _ in 0..task_pool_size {
             let worker = Worker::new_lifo();
             stealers.push(worker.stealer());
             local_queues.push(worker);
         }
         let global_queue = self.global_queue.clone();
         let stealers_arc = Arc::new(stealers);
+        self.stealers = Some(stealers_arc.clone());
         for local_queue in local_queues {
             let local_queue = local_queue;
             let global_queue = global_queue.clone();
             let stealers_arc = stealers_arc.clone();
-            tokio::spawn(async move {
+            let worker_lifecycle = lifecycle.clone();
+            lifecycle.spawn(async move {
                 loop {
-                    if let Some(task) = get_task(&local_queue, &global_queue, &stealers_arc) {
-                        task.run().await;
+                    if let Some(task_run) = get_task(&local_queue, &global_queue, &stealers_arc) {
+                        task_run.execute().await;
+                    } else if worker_lifecycle.is_dispatch_stopped() {
+                        // 主reactor已经停止且没有待运行的任务
+                        break;
                     } else {
-                        tokio::time::sleep(Duration::from_millis(DEFAULT_TICK_TIME)).await;
+                        tokio::time::sleep(Duration::from_millis(tick_time)).await;
                     }
                 }
             });
@@ -54,10 +74,10 @@ impl TasksManager {
 
 #[inline(always)]
 fn get_task(
-    local_queue: &Worker<Arc<Box<dyn ITaskHandler>>>,
-    global_queue: &Arc<Injector<Arc<Box<dyn ITaskHandler>>>>,
-    stealers_arc: &Arc<Vec<Stealer<Arc<Box<dyn ITaskHandler>>>>>,
-) -> Option<Arc<Box<dyn ITaskHandler>>> {
+    local_queue: &Worker<TaskRun>,
+    global_queue: &Arc<Injector<TaskRun>>,
+    stealers_arc: &Arc<Vec<Stealer<TaskRun>>>,
+) -> Option<TaskRun> {
     // 1. local
     if let Some(event) = local_queue.pop() {
         return Some(event);
@@ -0,0 +1,80 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// ## 重试退避方式
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backoff {
+    /// 每次重试间隔固定时间
+    Fixed(Duration),
+    /// 第n次重试间隔`base * 2^(n-1)`，最大不超过`max`
+    Exponential { base: Duration, max: Duration },
+}
+
+/// ## 重试策略
+/// 任务函数返回`Err`后，由调度器将本次运行作为一次性任务重新放入时间轮，不影响任务原有的cron节奏
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryPolicy {
+    /// 最大尝试次数，包含第一次运行，如`3`表示最多重试2次
+    pub max_attempts: usize,
+    /// 退避方式
+    pub backoff: Backoff,
+    /// 在退避时间上额外增加`[0, jitter]`的随机延迟，避免大量任务同时重试
+    pub jitter: Duration,
+}
+
+impl RetryPolicy {
+    /// 固定间隔重试
+    pub fn fixed(max_attempts: usize, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            backoff: Backoff::Fixed(delay),
+            jitter: Duration::ZERO,
+        }
+    }
+
+    /// 指数退避重试
+    pub fn exponential(max_attempts: usize, base: Duration, max: Duration) -> Self {
+        Self {
+            max_attempts,
+            backoff: Backoff::Exponential { base, max },
+            jitter: Duration::ZERO,
+        }
+    }
+
+    /// 设置随机延迟上限
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// 第`attempt`次尝试失败后，距离下一次尝试的延迟，没有剩余尝试次数时返回`None`
+    pub(crate) fn delay_for(&self, attempt: usize) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let delay = match &self.backoff {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential { base, max } => {
+                let exponent = attempt.saturating_sub(1).min(31) as u32;
+                base.checked_mul(1 << exponent)
+                    .map_or(*max, |delay| delay.min(*max))
+            }
+        };
+        Some(delay + self.random_jitter())
+    }
+
+    fn random_jitter(&self) -> Duration {
+        let jitter_millis = self.jitter.as_millis() as u64;
+        if jitter_millis == 0 {
+            return Duration::ZERO;
+        }
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(jitter_millis);
+        Duration::from_millis(hasher.finish() % (jitter_millis + 1))
+    }
+}
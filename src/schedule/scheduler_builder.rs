@@ -0,0 +1,127 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::schedule::{
+    Clock, Scheduler, SchedulerError, SystemClock, TaskTimeZone,
+    clock::SharedClock,
+    config::{DEFAULT_ERROR_CODE_1004, SchedulerConfig},
+};
+
+/// ## 调度器构建器
+/// 通过`Scheduler::builder()`创建，未设置的项使用默认值：
+/// - tick间隔25毫秒
+/// - 32个副reactor
+/// - TaskActor的channel大小为8
+/// - 事件channel大小为1024
+/// - 毫秒时间轮10个槽位、间隔100毫秒；秒时间轮60个槽位、间隔1秒；分钟时间轮60个槽位、间隔1分钟；小时时间轮24个槽位、间隔1小时
+/// - 错过触发阈值1秒
+///
+/// 配置在`build`时统一检查，不合法时返回错误
+pub struct SchedulerBuilder {
+    config: SchedulerConfig,
+    clock: SharedClock,
+    default_timeout: Option<Duration>,
+    default_time_zone: TaskTimeZone,
+}
+
+impl Default for SchedulerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchedulerBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: SchedulerConfig::default(),
+            clock: Arc::new(SystemClock),
+            default_timeout: None,
+            default_time_zone: TaskTimeZone::Local,
+        }
+    }
+
+    /// 设置主reactor的tick间隔，需要大于0且不超过毫秒时间轮的槽位间隔
+    pub fn tick_time(mut self, tick_time: Duration) -> Self {
+        self.config.tick_time = tick_time.as_millis() as u64;
+        self
+    }
+
+    /// 设置运行无序任务的副reactor数量，需要大于0
+    pub fn task_pool_size(mut self, task_pool_size: usize) -> Self {
+        self.config.task_pool_size = task_pool_size;
+        self
+    }
+
+    /// 设置每个任务的TaskActor的channel大小，需要大于0
+    pub fn channel_size(mut self, channel_size: usize) -> Self {
+        self.config.channel_size = channel_size;
+        self
+    }
+
+    /// 设置`Scheduler::subscribe`事件channel的大小，需要大于0
+    pub fn event_channel_size(mut self, event_channel_size: usize) -> Self {
+        self.config.event_channel_size = event_channel_size;
+        self
+    }
+
+    /// 设置毫秒时间轮的槽位数量与槽位间隔
+    pub fn millisecond_time_wheel(mut self, slot_len: usize, interval: Duration) -> Self {
+        self.config.time_wheel_settings[0] = (slot_len, interval.as_millis() as u64);
+        self
+    }
+
+    /// 设置秒时间轮的槽位数量与槽位间隔
+    pub fn second_time_wheel(mut self, slot_len: usize, interval: Duration) -> Self {
+        self.config.time_wheel_settings[1] = (slot_len, interval.as_millis() as u64);
+        self
+    }
+
+    /// 设置分钟时间轮的槽位数量与槽位间隔
+    pub fn minute_time_wheel(mut self, slot_len: usize, interval: Duration) -> Self {
+        self.config.time_wheel_settings[2] = (slot_len, interval.as_millis() as u64);
+        self
+    }
+
+    /// 设置小时时间轮的槽位数量与槽位间隔
+    pub fn hour_time_wheel(mut self, slot_len: usize, interval: Duration) -> Self {
+        self.config.time_wheel_settings[3] = (slot_len, interval.as_millis() as u64);
+        self
+    }
+
+    /// 设置默认的错过触发阈值，任务触发晚于计划时间超过该值时按`MisfirePolicy`处理
+    /// 对未通过`TaskOptions::misfire_threshold`单独设置阈值的任务生效
+    pub fn misfire_threshold(mut self, misfire_threshold: Duration) -> Self {
+        self.config.misfire_threshold = misfire_threshold.as_millis() as u64;
+        self
+    }
+
+    /// 设置时钟，默认为系统时钟
+    pub fn clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// 设置默认的运行超时时间，等同于`Scheduler::set_default_timeout`
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// 设置默认时区，等同于`Scheduler::set_default_time_zone`
+    pub fn default_time_zone(mut self, time_zone: impl Into<TaskTimeZone>) -> Self {
+        self.default_time_zone = time_zone.into();
+        self
+    }
+
+    /// 检查配置并创建调度器，调度器的内部reactor在此时启动
+    pub fn build(self) -> Result<Scheduler, Box<dyn std::error::Error>> {
+        if let Err(reason) = self.config.validate() {
+            return Err(Box::new(SchedulerError(format!(
+                "{DEFAULT_ERROR_CODE_1004}: {reason}"
+            ))));
+        }
+        let mut scheduler = Scheduler::from_config(self.config, self.clock);
+        scheduler.default_timeout = self.default_timeout;
+        scheduler.default_time_zone = self.default_time_zone;
+        Ok(scheduler)
+    }
+}
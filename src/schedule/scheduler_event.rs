@@ -0,0 +1,137 @@
+#[cfg(feature = "jobs")]
+use std::path::PathBuf;
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use tokio::sync::broadcast;
+
+#[cfg(feature = "jobs")]
+use crate::schedule::JobsDiff;
+use crate::schedule::MisfirePolicy;
+#[cfg(feature = "metrics")]
+use crate::schedule::metrics::Metrics;
+
+/// ## 调度器事件
+/// 通过`Scheduler::subscribe`订阅，用于监控、审计等场景
+///
+/// 事件通过`tokio::sync::broadcast`分发，订阅者处理过慢时会丢失最早的事件并收到`RecvError::Lagged`
+///
+/// 部分事件只在开启对应feature时存在，之后也可能新增事件，匹配时需要保留`_`分支
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchedulerEvent {
+    /// 新增任务
+    TaskAdded { task_id: usize },
+    /// 任务触发，`scheduled`为按cron计划的触发时间，`actual`为实际触发时间
+    Fired {
+        task_id: usize,
+        scheduled: DateTime<Local>,
+        actual: DateTime<Local>,
+    },
+    /// 任务被`Scheduler::trigger_now`手动触发
+    Triggered {
+        task_id: usize,
+        actual: DateTime<Local>,
+    },
+    /// 任务运行成功
+    Completed { task_id: usize, duration: Duration },
+    /// 任务运行失败或超时
+    Failed {
+        task_id: usize,
+        error: String,
+        duration: Duration,
+    },
+    /// 任务运行被新的触发取消（`OverlapPolicy::Replace`）
+    Cancelled { task_id: usize, duration: Duration },
+    /// 上一次运行尚未结束（`OverlapPolicy::Skip`）或达到并发上限（`LimitBehavior::Drop/Coalesce`），跳过本次触发
+    Skipped {
+        task_id: usize,
+        scheduled: DateTime<Local>,
+    },
+    /// 任务暂停
+    Paused { task_id: usize },
+    /// 任务恢复运行
+    Resumed { task_id: usize },
+    /// 任务销毁
+    Destroyed { task_id: usize },
+    /// 任务不再触发，状态变为`TaskStatus::Completed`并从调度器中移除
+    Finished { task_id: usize },
+    /// 任务的cron更新
+    CronUpdated { task_id: usize, cron: String },
+    /// 任务触发时已经晚于计划时间超过阈值，按`policy`运行或跳过
+    Misfired {
+        task_id: usize,
+        scheduled: DateTime<Local>,
+        actual: DateTime<Local>,
+        policy: MisfirePolicy,
+    },
+    /// 任务定义文件重新加载成功
+    #[cfg(feature = "jobs")]
+    JobsReloaded { path: PathBuf, diff: JobsDiff },
+    /// 任务定义文件重新加载失败，之前加载的任务保持不变
+    #[cfg(feature = "jobs")]
+    JobsReloadFailed { path: PathBuf, error: String },
+}
+
+impl SchedulerEvent {
+    /// 事件对应的任务id，重新加载任务定义文件的事件没有对应的任务
+    pub fn task_id(&self) -> Option<usize> {
+        let task_id = match self {
+            SchedulerEvent::TaskAdded { task_id }
+            | SchedulerEvent::Fired { task_id, .. }
+            | SchedulerEvent::Triggered { task_id, .. }
+            | SchedulerEvent::Completed { task_id, .. }
+            | SchedulerEvent::Failed { task_id, .. }
+            | SchedulerEvent::Cancelled { task_id, .. }
+            | SchedulerEvent::Skipped { task_id, .. }
+            | SchedulerEvent::Paused { task_id }
+            | SchedulerEvent::Resumed { task_id }
+            | SchedulerEvent::Destroyed { task_id }
+            | SchedulerEvent::Finished { task_id }
+            | SchedulerEvent::CronUpdated { task_id, .. }
+            | SchedulerEvent::Misfired { task_id, .. } => task_id,
+            #[cfg(feature = "jobs")]
+            SchedulerEvent::JobsReloaded { .. } | SchedulerEvent::JobsReloadFailed { .. } => {
+                return None;
+            }
+        };
+        Some(*task_id)
+    }
+}
+
+/// ## 事件发送端
+/// 没有订阅者时事件直接丢弃，开启`metrics` feature时同时记录指标
+#[derive(Clone)]
+pub(crate) struct EventSender {
+    sender: broadcast::Sender<SchedulerEvent>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+}
+
+impl EventSender {
+    pub(crate) fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    pub(crate) fn send(&self, event: SchedulerEvent) {
+        #[cfg(feature = "metrics")]
+        self.metrics.record(&event);
+        let _ = self.sender.send(event);
+    }
+
+    #[cfg(feature = "metrics")]
+    pub(crate) fn get_metrics(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<SchedulerEvent> {
+        self.sender.subscribe()
+    }
+}
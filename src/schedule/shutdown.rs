@@ -0,0 +1,136 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::{sync::watch, task::AbortHandle};
+
+/// ## 停止方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// 立即中止所有正在运行的任务
+    Immediate,
+    /// 不再触发新的任务，等待已经触发的任务运行结束，超过`deadline`后中止剩余的任务
+    Drain { deadline: Duration },
+}
+
+/// ## 停止句柄
+/// 通过`Scheduler::shutdown_handle`获取，可以在其他异步任务中停止调度器，如收到`ctrl_c`信号时
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    lifecycle: SharedLifecycle,
+}
+
+impl ShutdownHandle {
+    /// 停止调度器，等同于`Scheduler::shutdown`
+    pub async fn shutdown(&self, mode: ShutdownMode) {
+        self.lifecycle.shutdown(mode).await;
+    }
+
+    /// 调度器是否已经开始停止
+    pub fn is_shutdown(&self) -> bool {
+        self.lifecycle.is_shutdown()
+    }
+}
+
+pub(crate) type SharedLifecycle = Arc<Lifecycle>;
+
+/// ## 调度器生命周期
+/// 主reactor、副reactor与TaskActor都通过`spawn`启动，停止时统一等待或中止
+pub(crate) struct Lifecycle {
+    /// 是否已经开始停止
+    shutdown: watch::Sender<bool>,
+    /// 主reactor是否已经退出，退出后不会再有新的无序任务进入全局队列
+    dispatch_stopped: AtomicBool,
+    /// 尚未退出的异步任务数量
+    alive: watch::Sender<usize>,
+    /// 是否已经停止完成
+    terminated: watch::Sender<bool>,
+    abort_handles: Mutex<Vec<AbortHandle>>,
+}
+
+impl Lifecycle {
+    pub(crate) fn new_shared() -> SharedLifecycle {
+        Arc::new(Self {
+            shutdown: watch::Sender::new(false),
+            dispatch_stopped: AtomicBool::new(false),
+            alive: watch::Sender::new(0),
+            terminated: watch::Sender::new(false),
+            abort_handles: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub(crate) fn handle(self: &Arc<Self>) -> ShutdownHandle {
+        ShutdownHandle {
+            lifecycle: self.clone(),
+        }
+    }
+
+    /// 启动受生命周期管理的异步任务，任务退出或被中止时计数减一
+    pub(crate) fn spawn(self: &Arc<Self>, future: impl Future<Output = ()> + Send + 'static) {
+        self.alive.send_modify(|alive| *alive += 1);
+        let guard = AliveGuard(self.clone());
+        let join_handle = tokio::spawn(async move {
+            let _guard = guard;
+            future.await;
+        });
+        if let Ok(mut abort_handles) = self.abort_handles.lock() {
+            abort_handles.retain(|abort_handle| !abort_handle.is_finished());
+            abort_handles.push(join_handle.abort_handle());
+        }
+    }
+
+    pub(crate) fn is_shutdown(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+
+    pub(crate) fn stop_dispatch(&self) {
+        self.dispatch_stopped.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_dispatch_stopped(&self) -> bool {
+        self.dispatch_stopped.load(Ordering::Acquire)
+    }
+
+    pub(crate) async fn shutdown(&self, mode: ShutdownMode) {
+        self.shutdown.send_replace(true);
+        let mut alive = self.alive.subscribe();
+        if let ShutdownMode::Drain { deadline } = mode {
+            let _ = tokio::time::timeout(deadline, alive.wait_for(|alive| *alive == 0)).await;
+        }
+        self.abort();
+        // 被中止的任务在下一次让出执行权时才会退出
+        let _ = alive.wait_for(|alive| *alive == 0).await;
+        self.terminated.send_replace(true);
+    }
+
+    /// 中止所有异步任务，不等待其退出
+    pub(crate) fn abort(&self) {
+        self.shutdown.send_replace(true);
+        if let Ok(mut abort_handles) = self.abort_handles.lock() {
+            for abort_handle in abort_handles.drain(..) {
+                abort_handle.abort();
+            }
+        }
+    }
+
+    pub(crate) async fn wait_terminated(&self) {
+        let mut terminated = self.terminated.subscribe();
+        let _ = terminated.wait_for(|terminated| *terminated).await;
+    }
+}
+
+struct AliveGuard(SharedLifecycle);
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        self.0.alive.send_modify(|alive| *alive -= 1);
+    }
+}
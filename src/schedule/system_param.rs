@@ -0,0 +1,119 @@
+use std::{
+    any::{Any, TypeId, type_name},
+    collections::HashMap,
+    fmt::Display,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
+
+use crate::schedule::TaskContext;
+
+/// ## 共享资源
+/// 通过`Scheduler::insert_resource`插入，任务函数通过`Res<T>`参数获取
+#[derive(Clone, Default)]
+pub struct Resources {
+    resources: Arc<RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>>,
+}
+
+impl Resources {
+    pub(crate) fn insert<R: Send + Sync + 'static>(&self, resource: R) {
+        if let Ok(mut resources) = self.resources.write() {
+            resources.insert(TypeId::of::<R>(), Arc::new(resource));
+        }
+    }
+
+    /// 获取类型为`R`的资源
+    pub fn get<R: Send + Sync + 'static>(&self) -> Option<Arc<R>> {
+        let resources = self.resources.read().ok()?;
+        let resource = resources.get(&TypeId::of::<R>())?.clone();
+        resource.downcast::<R>().ok()
+    }
+
+    /// 是否存在类型为`R`的资源
+    pub fn contains<R: Send + Sync + 'static>(&self) -> bool {
+        self.resources
+            .read()
+            .map(|resources| resources.contains_key(&TypeId::of::<R>()))
+            .unwrap_or(false)
+    }
+}
+
+/// ## 任务函数参数
+/// 实现了`SystemParam`的类型可以作为任务函数的参数，由调度器在每次运行时自动填充
+pub trait SystemParam: Sized + Send + 'static {
+    /// 注册任务时检查参数是否可以获取，不可获取时返回缺失的类型名
+    fn validate(resources: &Resources) -> Result<(), String>;
+
+    /// 每次运行时获取参数
+    fn fetch(context: &TaskContext, resources: &Resources) -> Option<Self>;
+}
+
+/// ## 资源参数
+/// 获取通过`Scheduler::insert_resource`插入的共享资源
+pub struct Res<R: Send + Sync + 'static>(Arc<R>);
+
+impl<R: Send + Sync + 'static> Clone for Res<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R: Send + Sync + 'static> Deref for Res<R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<R: Send + Sync + 'static> SystemParam for Res<R> {
+    fn validate(resources: &Resources) -> Result<(), String> {
+        if resources.contains::<R>() {
+            Ok(())
+        } else {
+            Err(type_name::<R>().to_string())
+        }
+    }
+
+    fn fetch(_context: &TaskContext, resources: &Resources) -> Option<Self> {
+        resources.get::<R>().map(Res)
+    }
+}
+
+/// ## 任务id参数
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TaskId(pub usize);
+
+impl Deref for TaskId {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for TaskId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl SystemParam for TaskId {
+    fn validate(_resources: &Resources) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fetch(context: &TaskContext, _resources: &Resources) -> Option<Self> {
+        Some(TaskId(context.task_id()))
+    }
+}
+
+impl SystemParam for TaskContext {
+    fn validate(_resources: &Resources) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fetch(context: &TaskContext, _resources: &Resources) -> Option<Self> {
+        Some(context.clone())
+    }
+}
@@ -1,21 +1,29 @@
-use std::{pin::Pin, sync::Arc};
+use std::{pin::Pin, sync::Arc, time::Duration};
 
-use crate::schedule::{RepeatModel, config::DEFAULT_CHANNEL_SIZE};
+use crate::schedule::{
+    MisfirePolicy, OverlapPolicy, RepeatModel, Schedule, SchedulerEvent, TaskContext, TaskOptions,
+    TaskTimeZone, clock::SharedClock, scheduler_event::EventSender, shutdown::SharedLifecycle,
+    task_record::SharedTaskRecord, time_wheel::TierTimeWheel,
+};
 use chrono::{DateTime, Local};
-use cron::Schedule;
-use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::sync::mpsc::{Receiver, Sender, UnboundedSender, channel, unbounded_channel};
+
+mod task_run;
+
+pub(crate) use task_run::{TaskRetry, TaskRun};
 
 pub(crate) trait TaskPollTrait {
     async fn get_target_date_time(&mut self) -> Option<DateTime<Local>>;
     fn get_task_order_type(&mut self) -> TaskOrderType;
     fn get_task_signal_sender(&mut self) -> Sender<TaskSignal>;
-    async fn get_handle(&mut self) -> Option<Arc<Box<dyn ITaskHandler>>>;
+    async fn get_task_run(&mut self, context: TaskContext) -> Option<TaskRun>;
     async fn tick_repeat_model(&mut self) -> Option<bool>;
     async fn get_next_datetime(&self) -> Option<DateTime<Local>>;
     async fn set_target_date_time(&mut self, target_datetime: DateTime<Local>);
 }
 pub(crate) trait TaskActorTrait {
     fn get_handle(&self) -> Arc<Box<dyn ITaskHandler>>;
+    fn get_task_run(&self, context: TaskContext) -> TaskRun;
     fn get_next_datetime(&self) -> Option<DateTime<Local>>;
     fn tick_repeat_model(&mut self) -> bool;
     fn set_target_date_time(&mut self, target_datetime: DateTime<Local>);
@@ -23,43 +31,55 @@ pub(crate) trait TaskActorTrait {
 }
 
 /// 任务信号
-#[derive(Clone)]
 pub(crate) enum TaskSignal {
-    /// 获取handle
-    GetHandle(Sender<Arc<Box<dyn ITaskHandler>>>),
+    /// 获取一次待运行的任务
+    GetTaskRun(TaskContext, Sender<TaskRun>),
     /// 运行一次handle
-    RunHandle,
+    RunHandle(TaskContext),
+    /// 运行一次已构建的任务（重试）
+    RunTask(TaskRun),
     /// 获取下次运行时间
     GetNextDatetime(Sender<Option<DateTime<Local>>>),
     /// 获取是否需要重复运行
     TickRepeatModel(Sender<bool>),
     /// 设置目标时间
     SetTargetDateTime(DateTime<Local>),
-    /// 获取目标时间
-    GetTargetDateTime(Sender<DateTime<Local>>),
+    /// 获取目标时间，任务已完成时为`None`
+    GetTargetDateTime(Sender<Option<DateTime<Local>>>),
     /// 销毁
     Destory,
     /// 暂停
     Pause(Sender<TaskActor>),
     /// 更新cron
-    UpdateCron(Schedule),
+    UpdateCron(Box<cron::Schedule>),
 }
 
-pub(crate) enum TaskOrderType {
+/// ## 任务的运行方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "persistence",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum TaskOrderType {
     /// 有序
     Order,
     /// 无序
     Disorder,
 }
 
-#[derive(Clone)]
-pub(crate) enum TaskStatus {
+/// ## 任务状态
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaskStatus {
     /// 暂停,挂起
     Pause,
     /// 销毁
     Destory,
     /// 运行中
     Running,
+    /// 已完成，重复次数用尽、超出`RepeatModel::Until`的结束时间或一次性的计划已经触发，不再触发
+    Completed,
 }
 
 pub(crate) struct Task {
@@ -67,41 +87,128 @@ pub(crate) struct Task {
     task_order_type: TaskOrderType,
     task_status: TaskStatus,
     task_id: usize,
+    run_count: usize,
+    /// 待重试的运行，仅重试任务持有
+    retry_run: Option<TaskRun>,
+    /// 固定延迟的间隔，运行结束后才放回时间轮
+    fixed_delay: Option<Duration>,
+    overlap_policy: OverlapPolicy,
+    misfire_policy: MisfirePolicy,
+    /// 错过触发阈值，未设置时使用调度器的默认阈值
+    misfire_threshold: Option<Duration>,
+    record: Option<SharedTaskRecord>,
 }
 
 unsafe impl Send for Task {}
 unsafe impl Sync for Task {}
 
 impl Task {
+    /// 返回时间轮中的任务与尚未启动的TaskActor，由调用方启动或作为暂停中的任务保存
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
-        cron_schedule: cron::Schedule,
+        schedule: Schedule,
         handle: Arc<Box<dyn ITaskHandler>>,
         repeat_model: RepeatModel,
         target_datetime: DateTime<Local>,
         task_order_type: TaskOrderType,
         task_id: usize,
+        record: SharedTaskRecord,
+        options: TaskOptions,
+        time_wheel: &Arc<TierTimeWheel>,
+        channel_size: usize,
+        task_status: TaskStatus,
+    ) -> (Self, TaskActor) {
+        let overlap_policy = options.overlap_policy.unwrap_or_default();
+        let fixed_delay = schedule.fixed_delay();
+        let misfire_policy = options.misfire_policy.unwrap_or_default();
+        let misfire_threshold = options.misfire_threshold;
+        let (task_signal_sender, task_actor) = TaskActor::new(
+            task_id,
+            schedule,
+            handle,
+            repeat_model,
+            target_datetime,
+            task_order_type,
+            record.clone(),
+            options,
+            time_wheel,
+            channel_size,
+        );
+        let task = Self {
+            task_signal_sender,
+            task_order_type,
+            task_status,
+            task_id,
+            run_count: 0,
+            retry_run: None,
+            fixed_delay,
+            overlap_policy,
+            misfire_policy,
+            misfire_threshold,
+            record: Some(record),
+        };
+        (task, task_actor)
+    }
+
+    /// 构建一次性的重试任务，触发后不再放回时间轮
+    pub(crate) fn new_retry(
+        task_signal_sender: Sender<TaskSignal>,
+        task_order_type: TaskOrderType,
+        task_run: TaskRun,
     ) -> Self {
+        let context = task_run.get_context();
         Self {
-            task_signal_sender: TaskActor::new(
-                cron_schedule,
-                handle,
-                repeat_model,
-                target_datetime,
-            ),
+            task_signal_sender,
             task_order_type,
             task_status: TaskStatus::Running,
-            task_id,
+            task_id: context.task_id(),
+            run_count: context.run_count(),
+            retry_run: Some(task_run),
+            fixed_delay: None,
+            overlap_policy: OverlapPolicy::Parallel,
+            misfire_policy: MisfirePolicy::FireOnce,
+            misfire_threshold: None,
+            record: None,
         }
     }
 
+    pub(crate) fn is_retry(&self) -> bool {
+        self.retry_run.is_some()
+    }
+
+    pub(crate) fn take_retry_run(&mut self) -> Option<TaskRun> {
+        self.retry_run.take()
+    }
+
+    pub(crate) fn get_retry_run(&self) -> Option<&TaskRun> {
+        self.retry_run.as_ref()
+    }
+
+    pub(crate) fn get_fixed_delay(&self) -> Option<Duration> {
+        self.fixed_delay
+    }
+
+    pub(crate) fn get_misfire_policy(&self) -> MisfirePolicy {
+        self.misfire_policy
+    }
+
+    pub(crate) fn get_misfire_threshold(&self) -> Option<Duration> {
+        self.misfire_threshold
+    }
+
     pub(crate) fn is_running(&self) -> bool {
         match self.task_status {
             TaskStatus::Pause => false,
             TaskStatus::Destory => false,
             TaskStatus::Running => true,
+            TaskStatus::Completed => false,
         }
     }
 
+    pub(crate) fn get_status(&self) -> TaskStatus {
+        self.task_status.clone()
+    }
+
     pub(crate) fn set_status(&mut self, status: TaskStatus) {
         self.task_status = status;
     }
@@ -113,6 +220,30 @@ impl Task {
     pub(crate) fn get_sender(&self) -> Sender<TaskSignal> {
         self.task_signal_sender.clone()
     }
+
+    /// 按重叠策略判断本次触发是否运行，`Replace`时先取消已触发尚未结束的运行
+    /// 运行从触发时起计入，排队中尚未开始的运行同样视为重叠
+    pub(crate) fn check_overlap(&self) -> bool {
+        let Some(Ok(record)) = self.record.as_ref().map(|record| record.lock()) else {
+            return true;
+        };
+        match self.overlap_policy {
+            OverlapPolicy::Queue | OverlapPolicy::Parallel => true,
+            OverlapPolicy::Skip => record.running == 0,
+            OverlapPolicy::Replace => {
+                if record.running > 0 {
+                    record.cancel_running();
+                }
+                true
+            }
+        }
+    }
+
+    /// 记录一次触发，并构建本次运行的上下文
+    pub(crate) fn next_context(&mut self, scheduled_time: DateTime<Local>) -> TaskContext {
+        self.run_count += 1;
+        TaskContext::new(self.task_id, scheduled_time, self.run_count)
+    }
 }
 
 impl TaskPollTrait for Task {
@@ -122,35 +253,24 @@ impl TaskPollTrait for Task {
             .task_signal_sender
             .send(TaskSignal::GetTargetDateTime(tx))
             .await;
-        if let Some(datetime) = rx.recv().await {
-            return Some(datetime);
-        } else {
-            None
-        }
+        rx.recv().await.flatten()
     }
 
     fn get_task_order_type(&mut self) -> TaskOrderType {
-        match self.task_order_type {
-            TaskOrderType::Order => TaskOrderType::Order,
-            TaskOrderType::Disorder => TaskOrderType::Disorder,
-        }
+        self.task_order_type
     }
 
     fn get_task_signal_sender(&mut self) -> Sender<TaskSignal> {
         self.task_signal_sender.clone()
     }
 
-    async fn get_handle(&mut self) -> Option<Arc<Box<dyn ITaskHandler>>> {
+    async fn get_task_run(&mut self, context: TaskContext) -> Option<TaskRun> {
         let (tx, mut rx) = channel(1);
         let _ = self
             .task_signal_sender
-            .send(TaskSignal::GetHandle(tx))
+            .send(TaskSignal::GetTaskRun(context, tx))
             .await;
-        if let Some(handle) = rx.recv().await {
-            return Some(handle);
-        } else {
-            None
-        }
+        rx.recv().await
     }
 
     async fn tick_repeat_model(&mut self) -> Option<bool> {
@@ -159,11 +279,7 @@ impl TaskPollTrait for Task {
             .task_signal_sender
             .send(TaskSignal::TickRepeatModel(tx))
             .await;
-        if let Some(result) = rx.recv().await {
-            return Some(result);
-        } else {
-            None
-        }
+        rx.recv().await
     }
 
     async fn get_next_datetime(&self) -> Option<DateTime<Local>> {
@@ -172,11 +288,7 @@ impl TaskPollTrait for Task {
             .task_signal_sender
             .send(TaskSignal::GetNextDatetime(tx))
             .await;
-        if let Some(result) = rx.recv().await {
-            return result;
-        } else {
-            None
-        }
+        rx.recv().await.flatten()
     }
 
     async fn set_target_date_time(&mut self, target_datetime: DateTime<Local>) {
@@ -189,77 +301,223 @@ impl TaskPollTrait for Task {
 
 /// ## 任务actor
 pub(crate) struct TaskActor {
-    cron_schedule: cron::Schedule,
+    task_id: usize,
+    schedule: Schedule,
     handle: Arc<Box<dyn ITaskHandler>>,
     repeat_model: RepeatModel,
     target_datetime: DateTime<Local>,
+    /// 生效时间，早于该时间不触发
+    starting_at: Option<DateTime<Local>>,
+    /// `FireAll`时从本次目标时间开始计算下一次触发时间，依次补运行错过的触发
+    misfire_policy: MisfirePolicy,
+    record: SharedTaskRecord,
+    retry: Option<TaskRetry>,
+    timeout: Option<Duration>,
+    task_order_type: TaskOrderType,
+    time_zone: TaskTimeZone,
+    clock: SharedClock,
+    events: EventSender,
+    lifecycle: SharedLifecycle,
     receiver: Receiver<TaskSignal>,
+    /// 依次运行有序任务，TaskActor运行期间存在，使TaskActor在任务运行时仍能响应时间轮
+    runner: Option<UnboundedSender<TaskRun>>,
+    /// runner运行期间持有，恢复后新的runner等待暂停前的runner运行完已经排队的任务，同一时间只有一个runner运行
+    runner_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl TaskActor {
+    #[allow(clippy::too_many_arguments)]
     fn new(
-        cron_schedule: cron::Schedule,
+        task_id: usize,
+        schedule: Schedule,
         handle: Arc<Box<dyn ITaskHandler>>,
         repeat_model: RepeatModel,
         target_datetime: DateTime<Local>,
-    ) -> Sender<TaskSignal> {
-        let (tx, rx) = channel::<TaskSignal>(DEFAULT_CHANNEL_SIZE);
+        task_order_type: TaskOrderType,
+        record: SharedTaskRecord,
+        options: TaskOptions,
+        time_wheel: &Arc<TierTimeWheel>,
+        channel_size: usize,
+    ) -> (Sender<TaskSignal>, Self) {
+        let (tx, rx) = channel::<TaskSignal>(channel_size);
+        let retry = options.retry_policy.map(|retry_policy| {
+            TaskRetry::new(retry_policy, Arc::downgrade(time_wheel), tx.downgrade())
+        });
         let task_actor = Self {
-            cron_schedule,
+            task_id,
+            schedule,
             handle,
             repeat_model,
             target_datetime,
+            starting_at: options.starting_at,
+            misfire_policy: options.misfire_policy.unwrap_or_default(),
+            record,
+            retry,
+            timeout: options.timeout,
+            task_order_type,
+            time_zone: options.time_zone.unwrap_or_default(),
+            clock: time_wheel.get_clock(),
+            events: time_wheel.get_events(),
+            lifecycle: time_wheel.get_lifecycle(),
             receiver: rx,
+            runner: None,
+            runner_lock: Arc::new(tokio::sync::Mutex::new(())),
         };
-        task_actor.start_actor();
-        tx
+        (tx, task_actor)
     }
 
     pub(crate) fn start_actor(self) {
         let mut task_actor = self;
-        tokio::spawn(async move {
+        let lifecycle = task_actor.lifecycle.clone();
+        let mut shutdown = lifecycle.subscribe();
+        let (runner, mut task_runs) = unbounded_channel::<TaskRun>();
+        task_actor.runner = Some(runner);
+        let runner_lock = task_actor.runner_lock.clone();
+        // TaskActor退出或暂停时释放发送端，运行完已经排队的任务后退出
+        lifecycle.spawn(async move {
+            let _running = runner_lock.lock_owned().await;
+            while let Some(task_run) = task_runs.recv().await {
+                task_run.execute().await;
+            }
+        });
+        lifecycle.spawn(async move {
             loop {
-                if let Some(task_signal) = task_actor.get_signal().await {
-                    match task_signal {
-                        TaskSignal::GetHandle(sender) => {
-                            let _ = sender.send(task_actor.get_handle()).await;
-                        }
-                        TaskSignal::RunHandle => {
-                            let _ = task_actor.get_handle().run().await;
-                        }
-                        TaskSignal::GetNextDatetime(sender) => {
-                            let _ = sender.send(task_actor.get_next_datetime()).await;
-                        }
-                        TaskSignal::TickRepeatModel(sender) => {
-                            let _ = sender.send(task_actor.tick_repeat_model()).await;
-                        }
-                        TaskSignal::SetTargetDateTime(date_time) => {
-                            task_actor.set_target_date_time(date_time);
-                        }
-                        TaskSignal::GetTargetDateTime(sender) => {
-                            let _ = sender.send(task_actor.get_target_date_time()).await;
-                        }
-                        TaskSignal::Destory => break,
-                        TaskSignal::Pause(sender) => {
-                            sender.send(task_actor).await;
-                            break;
-                        }
-                        TaskSignal::UpdateCron(schedule) => {
-                            task_actor.cron_schedule = schedule;
-                            if let Some(datetime) = task_actor.cron_schedule.upcoming(Local).next()
-                            {
-                                task_actor.target_datetime = datetime;
-                            }
-                        }
+                let task_signal = tokio::select! {
+                    biased;
+                    task_signal = task_actor.get_signal() => Ok(task_signal),
+                    _ = shutdown.wait_for(|shutdown| *shutdown) => Err(()),
+                };
+                let Ok(task_signal) = task_signal else {
+                    task_actor.drain_signals().await;
+                    break;
+                };
+                // 所有发送端都已释放，任务不会再被触发
+                let Some(task_signal) = task_signal else {
+                    break;
+                };
+                match task_signal {
+                    TaskSignal::GetTaskRun(context, sender) => {
+                        let _ = sender.send(task_actor.get_task_run(context)).await;
+                    }
+                    TaskSignal::RunHandle(context) => {
+                        task_actor.run(task_actor.get_task_run(context));
+                    }
+                    TaskSignal::RunTask(task_run) => {
+                        task_actor.run(task_run);
+                    }
+                    TaskSignal::GetNextDatetime(sender) => {
+                        let _ = sender.send(task_actor.get_next_datetime()).await;
+                    }
+                    TaskSignal::TickRepeatModel(sender) => {
+                        let _ = sender.send(task_actor.tick_repeat_model()).await;
+                    }
+                    TaskSignal::SetTargetDateTime(date_time) => {
+                        task_actor.set_target_date_time(date_time);
+                    }
+                    TaskSignal::GetTargetDateTime(sender) => {
+                        let target_datetime =
+                            (!task_actor.is_completed()).then(|| task_actor.get_target_date_time());
+                        let _ = sender.send(target_datetime).await;
+                    }
+                    TaskSignal::Destory => break,
+                    TaskSignal::Pause(sender) => {
+                        task_actor.runner = None;
+                        let _ = sender.send(task_actor).await;
+                        break;
+                    }
+                    TaskSignal::UpdateCron(schedule) => {
+                        task_actor.update_cron(schedule);
                     }
                 }
             }
         });
     }
 
+    /// 调度器停止时不再接收信号，只运行已经触发的任务
+    async fn drain_signals(&mut self) {
+        self.receiver.close();
+        while let Some(task_signal) = self.receiver.recv().await {
+            match task_signal {
+                TaskSignal::RunHandle(context) => {
+                    self.run(self.get_task_run(context));
+                }
+                TaskSignal::RunTask(task_run) => {
+                    self.run(task_run);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 交给runner排队运行
+    fn run(&self, task_run: TaskRun) {
+        if let Some(runner) = self.runner.as_ref() {
+            let _ = runner.send(task_run);
+        }
+    }
+
     async fn get_signal(&mut self) -> Option<TaskSignal> {
         self.receiver.recv().await
     }
+
+    /// 恢复运行前将目标时间重置为下一次触发时间，避免暂停期间错过的时间点在恢复后立即触发
+    /// 只触发一次的计划保留原有的目标时间
+    pub(crate) fn reset_target_date_time(&mut self) {
+        if self.schedule.is_one_shot() {
+            return;
+        }
+        let now = self.clock.now();
+        let after = self
+            .starting_at
+            .map_or(now, |starting_at| now.max(starting_at));
+        match self.schedule.first_after(&self.time_zone, &after) {
+            Some(datetime) => self.set_target_date_time(datetime),
+            None => self.sync_record(None),
+        }
+    }
+
+    /// 更新cron并从当前时间开始重新计算目标时间，运行次数与剩余运行次数保持不变
+    pub(crate) fn update_cron(&mut self, schedule: Box<cron::Schedule>) {
+        self.schedule = Schedule::Cron(schedule);
+        if let Ok(mut record) = self.record.lock() {
+            record.schedule = self.schedule.clone();
+        }
+        self.reset_target_date_time();
+    }
+
+    /// 超出`RepeatModel::Until`结束时间的触发时间视为不再触发
+    fn within_window(&self, datetime: Option<DateTime<Local>>) -> Option<DateTime<Local>> {
+        let until = self.repeat_model.until();
+        datetime.filter(|datetime| until.is_none_or(|until| *datetime <= until))
+    }
+
+    pub(crate) fn is_completed(&self) -> bool {
+        self.record
+            .lock()
+            .is_ok_and(|record| record.status == TaskStatus::Completed)
+    }
+
+    /// 同步下一次触发时间与剩余运行次数到任务记录
+    /// 不再触发时将运行中或暂停中的任务标记为已完成，TaskManager在下一次修改任务时移除该任务
+    fn sync_record(&self, next_fire: Option<DateTime<Local>>) {
+        if let Ok(mut record) = self.record.lock() {
+            if next_fire.is_none()
+                && matches!(record.status, TaskStatus::Running | TaskStatus::Pause)
+            {
+                record.status = TaskStatus::Completed;
+                #[cfg(feature = "tracing")]
+                tracing::info!(task_id = self.task_id, "task completed");
+                self.events.send(SchedulerEvent::Finished {
+                    task_id: self.task_id,
+                });
+            }
+            record.next_fire = next_fire;
+            record.repeat_remaining = match self.repeat_model {
+                RepeatModel::Once => Some(usize::from(next_fire.is_some())),
+                _ => self.repeat_model.remaining(),
+            };
+        }
+    }
 }
 
 impl TaskActorTrait for TaskActor {
@@ -267,30 +525,70 @@ impl TaskActorTrait for TaskActor {
         self.handle.clone()
     }
 
+    fn get_task_run(&self, context: TaskContext) -> TaskRun {
+        TaskRun::new(
+            self.get_handle(),
+            context,
+            self.record.clone(),
+            self.retry.clone(),
+            self.timeout,
+            self.task_order_type,
+            self.clock.clone(),
+            self.events.clone(),
+        )
+    }
+
+    /// 时间轮允许任务提前少量时间触发，因此从当前时间与本次目标时间中较晚的一个开始计算，避免同一时间点重复触发
+    /// `MisfirePolicy::FireAll`从本次目标时间开始计算，错过的触发时间会在之后的tick中依次触发
     fn get_next_datetime(&self) -> Option<DateTime<Local>> {
-        self.cron_schedule.upcoming(Local).next()
+        let after = match self.misfire_policy {
+            MisfirePolicy::FireAll => self.target_datetime,
+            MisfirePolicy::FireOnce | MisfirePolicy::Skip => {
+                self.clock.now().max(self.target_datetime)
+            }
+        };
+        let next_datetime = self.within_window(self.schedule.next_after(
+            &self.time_zone,
+            &after,
+            &self.target_datetime,
+        ));
+        if next_datetime.is_none() {
+            self.sync_record(None);
+        }
+        next_datetime
     }
 
     fn tick_repeat_model(&mut self) -> bool {
-        match &mut self.repeat_model {
+        let repeat = match &mut self.repeat_model {
             RepeatModel::Once => false,
-            RepeatModel::Repetition => true,
+            RepeatModel::Repetition | RepeatModel::Until(_) => true,
             RepeatModel::Times(times) => {
-                *times -= 1;
-                if *times > 0 { true } else { false }
+                *times = times.saturating_sub(1);
+                *times > 0
             }
+        };
+        if !repeat {
+            self.sync_record(None);
         }
+        repeat
     }
 
     fn set_target_date_time(&mut self, target_datetime: DateTime<Local>) {
         self.target_datetime = target_datetime;
+        self.sync_record(self.within_window(Some(target_datetime)));
     }
 
     fn get_target_date_time(&mut self) -> DateTime<Local> {
-        self.target_datetime.clone()
+        self.target_datetime
     }
 }
 
+/// 任务函数返回的future，失败时输出错误信息
+pub(crate) type TaskFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'static>>;
+
 pub(crate) trait ITaskHandler: Send + Sync + 'static {
-    fn run(&self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+    fn run(&self, context: TaskContext) -> TaskFuture;
+
+    /// 任务函数的类型名
+    fn name(&self) -> &'static str;
 }
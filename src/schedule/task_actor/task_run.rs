@@ -0,0 +1,332 @@
+use std::{
+    sync::{Arc, Weak},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Local};
+use tokio::sync::{mpsc::WeakSender, watch};
+
+use crate::schedule::{
+    RetryPolicy, SchedulerEvent, TaskContext, TaskRunResult,
+    clock::SharedClock,
+    concurrency_limit::Acquired,
+    scheduler_event::EventSender,
+    task_actor::{ITaskHandler, Task, TaskOrderType, TaskPollTrait, TaskSignal, TaskStatus},
+    task_record::SharedTaskRecord,
+    time_wheel::TierTimeWheel,
+};
+
+/// ## 任务重试所需的信息
+/// 持有时间轮与TaskActor的弱引用，任务销毁后不会因为待重试的运行而无法释放
+#[derive(Clone)]
+pub(crate) struct TaskRetry {
+    retry_policy: RetryPolicy,
+    time_wheel: Weak<TierTimeWheel>,
+    task_signal_sender: WeakSender<TaskSignal>,
+}
+
+impl TaskRetry {
+    pub(crate) fn new(
+        retry_policy: RetryPolicy,
+        time_wheel: Weak<TierTimeWheel>,
+        task_signal_sender: WeakSender<TaskSignal>,
+    ) -> Self {
+        Self {
+            retry_policy,
+            time_wheel,
+            task_signal_sender,
+        }
+    }
+}
+
+/// ## 一次待运行的任务
+/// 由时间轮在触发时构建，有序任务在TaskActor内运行，无序任务放入全局队列由副reactor运行
+pub(crate) struct TaskRun {
+    handle: Arc<Box<dyn ITaskHandler>>,
+    context: TaskContext,
+    record: SharedTaskRecord,
+    retry: Option<TaskRetry>,
+    timeout: Option<Duration>,
+    task_order_type: TaskOrderType,
+    clock: SharedClock,
+    events: EventSender,
+    /// 固定延迟任务的时间轮任务，本次运行结束后放回时间轮
+    reschedule: Option<Reschedule>,
+    /// 从触发到运行结束期间计入任务记录，等待重试期间为`None`
+    pending: Option<PendingGuard>,
+}
+
+/// ## 运行结束后放回时间轮的任务
+pub(crate) struct Reschedule {
+    task: Box<Task>,
+    time_wheel: Weak<TierTimeWheel>,
+}
+
+impl Reschedule {
+    /// 从运行结束的时间开始，隔固定延迟后再次触发
+    async fn push(mut self, end_time: DateTime<Local>) {
+        let (Some(delay), Some(time_wheel)) =
+            (self.task.get_fixed_delay(), self.time_wheel.upgrade())
+        else {
+            return;
+        };
+        self.task.set_target_date_time(end_time + delay).await;
+        time_wheel.push_T_to_time_wheel(*self.task, delay.as_millis() as i64);
+    }
+}
+
+impl TaskRun {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        handle: Arc<Box<dyn ITaskHandler>>,
+        context: TaskContext,
+        record: SharedTaskRecord,
+        retry: Option<TaskRetry>,
+        timeout: Option<Duration>,
+        task_order_type: TaskOrderType,
+        clock: SharedClock,
+        events: EventSender,
+    ) -> Self {
+        let pending = Some(PendingGuard::new(&record));
+        Self {
+            handle,
+            context,
+            record,
+            retry,
+            timeout,
+            task_order_type,
+            clock,
+            events,
+            reschedule: None,
+            pending,
+        }
+    }
+
+    /// 待重试的运行到期后重新计入任务记录
+    pub(crate) fn set_pending(&mut self) {
+        self.pending = Some(PendingGuard::new(&self.record));
+    }
+
+    pub(crate) fn set_reschedule(&mut self, task: Task, time_wheel: Weak<TierTimeWheel>) {
+        self.reschedule = Some(Reschedule {
+            task: Box::new(task),
+            time_wheel,
+        });
+    }
+
+    pub(crate) fn get_context(&self) -> &TaskContext {
+        &self.context
+    }
+
+    /// 任务当前的状态，用于判断待重试的运行是否仍需执行
+    pub(crate) fn get_task_status(&self) -> TaskStatus {
+        self.record
+            .lock()
+            .map(|record| record.status.clone())
+            .unwrap_or(TaskStatus::Destory)
+    }
+
+    /// 运行一次任务，开启`tracing` feature时在`task_run` span内运行
+    /// 设置了并发上限时先获取许可，无法获取时放入任务的等待队列或放弃本次运行，不在副reactor中等待许可
+    /// 运行结束后沿用许可接着运行等待中的运行
+    pub(crate) async fn execute(self) {
+        let concurrency_limit = self
+            .record
+            .lock()
+            .ok()
+            .and_then(|record| record.concurrency_limit.clone());
+        let Some(concurrency_limit) = concurrency_limit else {
+            return self.instrumented_run().await;
+        };
+        let (mut task_run, mut permit) = match concurrency_limit.acquire(self) {
+            Acquired::Run(task_run, permit) => (task_run, permit),
+            Acquired::Waiting => return,
+            Acquired::Dropped(task_run) => return task_run.dropped().await,
+        };
+        loop {
+            // 等待期间已销毁的任务不再运行
+            if task_run.get_task_status() != TaskStatus::Destory {
+                task_run.instrumented_run().await;
+            }
+            match concurrency_limit.next_waiting(permit) {
+                Some((next_run, next_permit)) => (task_run, permit) = (next_run, next_permit),
+                None => return,
+            }
+        }
+    }
+
+    /// 因并发上限放弃本次运行
+    async fn dropped(mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            task_id = self.context.task_id(),
+            "task run dropped by concurrency limit"
+        );
+        self.events.send(SchedulerEvent::Skipped {
+            task_id: self.context.task_id(),
+            scheduled: self.context.scheduled_time(),
+        });
+        if let Some(reschedule) = self.reschedule.take() {
+            reschedule.push(self.clock.now()).await;
+        }
+    }
+
+    async fn instrumented_run(self) {
+        // 与任务记录的名称一致，未通过`TaskOptions::name`设置时为任务函数的类型名
+        #[cfg(feature = "tracing")]
+        let task_name = self
+            .record
+            .lock()
+            .map(|record| record.name.clone())
+            .unwrap_or_else(|_| self.handle.name().to_string());
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "task_run",
+            task_id = self.context.task_id(),
+            task_name = %task_name,
+            order_type = ?self.task_order_type,
+            scheduled_time = %self.context.scheduled_time(),
+            run_count = self.context.run_count(),
+            attempt = self.context.attempt(),
+        );
+        let future = self.run();
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+        future.await
+    }
+
+    async fn run(mut self) {
+        let reschedule = self.reschedule.take();
+        let mut pending = self.pending.take();
+        let mut context = self.context.clone();
+        context.set_start_time(self.clock.now());
+        if let Ok(mut record) = self.record.lock() {
+            record.last_fire = Some(context.start_time());
+        }
+        let start_instant = Instant::now();
+        let future = self.handle.run(context);
+        let timeout = self.timeout;
+        let future = async move {
+            match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, future).await {
+                    Ok(result) => TaskRunResult::from_result(result),
+                    // 超时后future被丢弃，本次运行视为失败
+                    Err(_) => TaskRunResult::TimedOut(timeout),
+                },
+                None => TaskRunResult::from_result(future.await),
+            }
+        };
+        // 排队期间已被取消的运行不再调用任务函数
+        let cancel = pending.as_mut().and_then(|pending| pending.cancel.as_mut());
+        let result = tokio::select! {
+            biased;
+            _ = cancelled(cancel) => TaskRunResult::Cancelled,
+            result = future => result,
+        };
+        let duration = start_instant.elapsed();
+        let task_id = self.context.task_id();
+        self.events.send(match result.error_message() {
+            Some(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %error, ?duration, "task run failed");
+                SchedulerEvent::Failed {
+                    task_id,
+                    error,
+                    duration,
+                }
+            }
+            None if result == TaskRunResult::Cancelled => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(?duration, "task run cancelled");
+                SchedulerEvent::Cancelled { task_id, duration }
+            }
+            None => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(?duration, "task run completed");
+                SchedulerEvent::Completed { task_id, duration }
+            }
+        });
+        let failed = result.is_failure();
+        if let Ok(mut record) = self.record.lock() {
+            record.stats.record(result, self.clock.now());
+        }
+        drop(pending);
+        if let Some(reschedule) = reschedule {
+            reschedule.push(self.clock.now()).await;
+        }
+        if failed {
+            self.retry();
+        }
+    }
+
+    /// 按重试策略将本次运行作为一次性任务重新放入时间轮
+    fn retry(self) {
+        let Some(retry) = self.retry.as_ref() else {
+            return;
+        };
+        let Some(delay) = retry.retry_policy.delay_for(self.context.attempt()) else {
+            return;
+        };
+        let (Some(time_wheel), Some(task_signal_sender)) = (
+            retry.time_wheel.upgrade(),
+            retry.task_signal_sender.upgrade(),
+        ) else {
+            return;
+        };
+        let task_order_type = self.task_order_type;
+        let retry_datetime = self.clock.now() + delay;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            next_attempt = self.context.attempt() + 1,
+            ?delay,
+            "task run retry scheduled"
+        );
+        let task_run = Self {
+            context: self.context.retry(retry_datetime),
+            ..self
+        };
+        time_wheel.push_T_to_time_wheel(
+            Task::new_retry(task_signal_sender, task_order_type, task_run),
+            delay.as_millis() as i64,
+        );
+    }
+}
+
+/// ## 已触发尚未结束的运行
+/// 从触发时起计入任务记录的运行次数，排队中的运行同样会被重叠策略看到，运行结束或被丢弃时减一
+/// 触发时订阅取消信号，之后取消时排队中的运行也会被取消
+struct PendingGuard {
+    record: SharedTaskRecord,
+    cancel: Option<watch::Receiver<u64>>,
+}
+
+impl PendingGuard {
+    fn new(record: &SharedTaskRecord) -> Self {
+        let cancel = record.lock().ok().map(|mut record| {
+            record.running += 1;
+            record.cancel.subscribe()
+        });
+        Self {
+            record: record.clone(),
+            cancel,
+        }
+    }
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut record) = self.record.lock() {
+            record.running = record.running.saturating_sub(1);
+        }
+    }
+}
+
+/// 触发之后收到取消信号时返回
+async fn cancelled(cancel: Option<&mut watch::Receiver<u64>>) {
+    if let Some(cancel) = cancel
+        && cancel.changed().await.is_ok()
+    {
+        return;
+    }
+    std::future::pending().await
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Local};
+
+/// ## 任务运行上下文
+/// 每次任务触发时由时间轮构建，并作为参数传递给`Fn(TaskContext) -> Fut`形式的任务函数
+#[derive(Clone, Debug)]
+pub struct TaskContext {
+    task_id: usize,
+    scheduled_time: DateTime<Local>,
+    start_time: DateTime<Local>,
+    run_count: usize,
+    attempt: usize,
+    manual: bool,
+}
+
+impl TaskContext {
+    pub(crate) fn new(task_id: usize, scheduled_time: DateTime<Local>, run_count: usize) -> Self {
+        Self {
+            task_id,
+            scheduled_time,
+            start_time: scheduled_time,
+            run_count,
+            attempt: 1,
+            manual: false,
+        }
+    }
+
+    /// 构建手动触发的上下文，不计入运行次数
+    pub(crate) fn manual(task_id: usize, scheduled_time: DateTime<Local>) -> Self {
+        Self {
+            manual: true,
+            ..Self::new(task_id, scheduled_time, 0)
+        }
+    }
+
+    /// 构建下一次重试的上下文
+    pub(crate) fn retry(&self, scheduled_time: DateTime<Local>) -> Self {
+        Self {
+            scheduled_time,
+            start_time: scheduled_time,
+            attempt: self.attempt + 1,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn set_start_time(&mut self, start_time: DateTime<Local>) {
+        self.start_time = start_time;
+    }
+
+    /// 任务id，与新增任务时返回的id一致
+    pub fn task_id(&self) -> usize {
+        self.task_id
+    }
+
+    /// 本次运行按cron计划的触发时间
+    pub fn scheduled_time(&self) -> DateTime<Local> {
+        self.scheduled_time
+    }
+
+    /// 本次运行实际开始执行的时间
+    pub fn start_time(&self) -> DateTime<Local> {
+        self.start_time
+    }
+
+    /// 本次是该任务的第几次运行（从1开始），手动触发的运行为0
+    pub fn run_count(&self) -> usize {
+        self.run_count
+    }
+
+    /// 本次运行是第几次尝试（从1开始），大于1时表示由重试策略触发的重试
+    pub fn attempt(&self) -> usize {
+        self.attempt
+    }
+
+    /// 本次运行是否由`Scheduler::trigger_now`手动触发
+    pub fn is_manual(&self) -> bool {
+        self.manual
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Local};
+
+use crate::schedule::{
+    OverlapPolicy, TaskRunResult,
+    task_actor::{TaskOrderType, TaskStatus},
+};
+
+/// ## 任务信息
+/// 通过`Scheduler::list_tasks`、`Scheduler::task_info`查询，已销毁的任务不再返回
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskInfo {
+    /// 任务id
+    pub id: usize,
+    /// 任务名称，未通过`TaskOptions::name`设置时为任务函数的类型名
+    pub name: String,
+    /// 标签
+    pub tags: Vec<String>,
+    /// cron表达式
+    pub cron: String,
+    /// 有序/无序
+    pub order_type: TaskOrderType,
+    /// 重叠策略
+    pub overlap_policy: OverlapPolicy,
+    /// 任务状态
+    pub status: TaskStatus,
+    /// 剩余运行次数，`None`表示不限（`RepeatModel::Repetition`）
+    pub repeat_remaining: Option<usize>,
+    /// 下一次触发时间，不再触发时为`None`
+    pub next_fire: Option<DateTime<Local>>,
+    /// 最近一次开始运行的时间
+    pub last_fire: Option<DateTime<Local>>,
+    /// 最近一次运行结果
+    pub last_result: Option<TaskRunResult>,
+}
@@ -1,12 +1,24 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, MutexGuard},
+};
 
+use chrono::{DateTime, Local};
 use cron::Schedule;
 use tokio::sync::{
     RwLock,
     mpsc::{Sender, channel},
 };
 
-use crate::schedule::task_actor::{TaskActor, TaskSignal, TaskStatus};
+use crate::schedule::{
+    SchedulerEvent, TaskInfo, TaskSnapshot, TaskStats,
+    scheduler_event::EventSender,
+    task_actor::{TaskActor, TaskOrderType, TaskSignal, TaskStatus},
+    task_record::{SharedTaskRecord, TaskRecord},
+};
+
+/// 等待时间轮同步的任务状态变更
+pub(crate) type NoticeList = Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>>;
 
 pub(crate) struct TaskManager {
     /// task_id计数器
@@ -15,121 +27,355 @@ pub(crate) struct TaskManager {
     pub(crate) id_task_mapping: Option<HashMap<usize, Sender<TaskSignal>>>,
     /// 被暂停的task
     pub(crate) idle_task: Option<HashMap<usize, TaskActor>>,
+    /// id-任务记录的映射
+    pub(crate) task_record_mapping: Option<HashMap<usize, SharedTaskRecord>>,
+    /// 名称-id的映射，只包含设置了名称的任务
+    pub(crate) name_mapping: Option<HashMap<String, usize>>,
     /// notice_list
-    pub(crate) notice_list: Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>>,
+    pub(crate) notice_list: NoticeList,
+    /// 事件发送端
+    pub(crate) events: EventSender,
 }
 
 impl TaskManager {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(events: EventSender) -> Self {
         Self {
             task_id_counter: 0,
             id_task_mapping: None,
             idle_task: None,
+            task_record_mapping: None,
+            name_mapping: None,
             notice_list: Arc::new(RwLock::new(None)),
+            events,
         }
     }
 
-    pub(crate) fn insert_new_task(&mut self, task_id: usize, sender: Sender<TaskSignal>) {
-        if let None = self.id_task_mapping.as_mut() {
-            self.id_task_mapping = Some(HashMap::new());
+    /// 暂停中的任务同时保存尚未启动的TaskActor，恢复时再启动
+    pub(crate) fn insert_new_task(
+        &mut self,
+        task_id: usize,
+        name: Option<String>,
+        sender: Sender<TaskSignal>,
+        record: SharedTaskRecord,
+        idle_actor: Option<TaskActor>,
+    ) {
+        self.remove_completed_tasks();
+        if let Some(task_actor) = idle_actor {
+            self.idle_task
+                .get_or_insert_with(HashMap::new)
+                .insert(task_id, task_actor);
+        }
+        if let Some(name) = name {
+            self.name_mapping
+                .get_or_insert_with(HashMap::new)
+                .insert(name, task_id);
+        }
+        self.id_task_mapping
+            .get_or_insert_with(HashMap::new)
+            .insert(task_id, sender);
+        self.task_record_mapping
+            .get_or_insert_with(HashMap::new)
+            .insert(task_id, record);
+        self.events.send(SchedulerEvent::TaskAdded { task_id });
+    }
+
+    /// 已完成的任务在下一次修改TaskManager时才会被移除，查询时视为已经移除
+    fn get_record(&self, task_id: usize) -> Option<MutexGuard<'_, TaskRecord>> {
+        let record = self.task_record_mapping.as_ref()?.get(&task_id)?;
+        record
+            .lock()
+            .ok()
+            .filter(|record| record.status != TaskStatus::Completed)
+    }
+
+    /// 移除已完成的任务，释放TaskActor
+    fn remove_completed_tasks(&mut self) {
+        let completed: Vec<usize> = self
+            .task_record_mapping
+            .iter()
+            .flatten()
+            .filter(|(_, record)| {
+                record
+                    .lock()
+                    .is_ok_and(|record| record.status == TaskStatus::Completed)
+            })
+            .map(|(task_id, _)| *task_id)
+            .collect();
+        for task_id in completed {
+            self.remove_task(task_id);
+        }
+    }
+
+    fn remove_task(&mut self, task_id: usize) {
+        if let Some(map) = self.task_record_mapping.as_mut() {
+            map.remove(&task_id);
         }
         if let Some(map) = self.id_task_mapping.as_mut() {
-            map.insert(task_id, sender);
+            map.remove(&task_id);
+        }
+        if let Some(idle_task) = self.idle_task.as_mut() {
+            idle_task.remove(&task_id);
+        }
+        if let Some(map) = self.name_mapping.as_mut() {
+            map.retain(|_, id| *id != task_id);
+        }
+    }
+
+    pub(crate) fn get_task_stats(&self, task_id: usize) -> Option<TaskStats> {
+        self.get_record(task_id).map(|record| record.stats.clone())
+    }
+
+    pub(crate) fn get_task_info(&self, task_id: usize) -> Option<TaskInfo> {
+        self.get_record(task_id).map(|record| record.info(task_id))
+    }
+
+    pub(crate) fn list_tasks(&self) -> Vec<TaskInfo> {
+        let mut tasks = vec![];
+        if let Some(map) = self.task_record_mapping.as_ref() {
+            for (task_id, record) in map {
+                if let Ok(record) = record.lock()
+                    && record.status != TaskStatus::Completed
+                {
+                    tasks.push(record.info(*task_id));
+                }
+            }
+        }
+        tasks.sort_by_key(|task_info| task_info.id);
+        tasks
+    }
+
+    pub(crate) fn get_task_id_by_name(&self, name: &str) -> Option<usize> {
+        let task_id = self.name_mapping.as_ref()?.get(name).copied()?;
+        self.get_record(task_id).map(|_| task_id)
+    }
+
+    /// 带有指定标签的任务id，按task_id升序排列
+    pub(crate) fn get_task_ids_by_tag(&self, tag: &str) -> Vec<usize> {
+        let mut task_ids = vec![];
+        if let Some(map) = self.task_record_mapping.as_ref() {
+            for (task_id, record) in map {
+                if let Ok(record) = record.lock()
+                    && record.status != TaskStatus::Completed
+                    && record.tags.iter().any(|task_tag| task_tag == tag)
+                {
+                    task_ids.push(*task_id);
+                }
+            }
+        }
+        task_ids.sort();
+        task_ids
+    }
+
+    /// 逐个更新带有指定标签的任务状态，返回更新成功的任务id
+    pub(crate) async fn update_task_status_by_tag(
+        &mut self,
+        tag: &str,
+        task_status: TaskStatus,
+    ) -> Vec<usize> {
+        let mut task_ids = vec![];
+        for task_id in self.get_task_ids_by_tag(tag) {
+            if self
+                .update_task_status_by_id(task_id, task_status.clone())
+                .await
+            {
+                task_ids.push(task_id);
+            }
+        }
+        task_ids
+    }
+
+    pub(crate) fn get_upcoming(
+        &self,
+        task_id: usize,
+        n: usize,
+        now: DateTime<Local>,
+    ) -> Option<Vec<DateTime<Local>>> {
+        self.get_record(task_id)
+            .map(|record| record.upcoming(n, now))
+    }
+
+    pub(crate) fn get_failing_tasks(&self) -> Vec<(usize, TaskStats)> {
+        let mut failing_tasks = vec![];
+        if let Some(map) = self.task_record_mapping.as_ref() {
+            for (task_id, record) in map {
+                if let Ok(record) = record.lock()
+                    && record.status != TaskStatus::Completed
+                    && record.stats.is_failing()
+                {
+                    failing_tasks.push((*task_id, record.stats.clone()));
+                }
+            }
+        }
+        failing_tasks.sort_by_key(|(task_id, _)| *task_id);
+        failing_tasks
+    }
+
+    /// 运行中任务的发送端与有序/无序类型，暂停中、已完成的任务返回`None`
+    pub(crate) fn get_trigger_target(
+        &mut self,
+        task_id: usize,
+    ) -> Option<(Sender<TaskSignal>, TaskOrderType)> {
+        self.remove_completed_tasks();
+        let task_order_type = self
+            .get_record(task_id)
+            .filter(|record| record.status == TaskStatus::Running)?
+            .overlap_policy
+            .order_type();
+        let sender = self.id_task_mapping.as_ref()?.get(&task_id)?;
+        Some((sender.clone(), task_order_type))
+    }
+
+    /// 可以保存的任务快照，按task_id升序排列
+    pub(crate) fn get_snapshots(&self) -> Vec<TaskSnapshot> {
+        let mut snapshots = vec![];
+        if let Some(map) = self.task_record_mapping.as_ref() {
+            let mut task_ids: Vec<&usize> = map.keys().collect();
+            task_ids.sort();
+            for task_id in task_ids {
+                if let Ok(record) = map[task_id].lock()
+                    && let Some(snapshot) = record.snapshot()
+                {
+                    snapshots.push(snapshot);
+                }
+            }
+        }
+        snapshots
+    }
+
+    /// 恢复任务时还原最近一次触发时间
+    pub(crate) fn set_last_fire(&self, task_id: usize, last_fire: Option<DateTime<Local>>) {
+        if let Some(record) = self
+            .task_record_mapping
+            .as_ref()
+            .and_then(|map| map.get(&task_id))
+            && let Ok(mut record) = record.lock()
+        {
+            record.last_fire = last_fire;
         }
     }
 
     pub(crate) fn get_new_id(&mut self) -> Option<usize> {
         if self.task_id_counter < usize::MAX {
             self.task_id_counter += 1;
-            return Some(self.task_id_counter.clone());
+            return Some(self.task_id_counter);
         }
         None
     }
 
-    pub(crate) fn get_notice_list(&self) -> Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>> {
+    pub(crate) fn get_notice_list(&self) -> NoticeList {
         self.notice_list.clone()
     }
 
+    async fn push_notice(&self, task_id: usize, task_status: TaskStatus) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(task_id, status = ?task_status, "task status changed");
+        if let Some(record) = self
+            .task_record_mapping
+            .as_ref()
+            .and_then(|map| map.get(&task_id))
+            && let Ok(mut record) = record.lock()
+        {
+            record.status = task_status.clone();
+        }
+        let mut mutex = self.notice_list.write().await;
+        if let Some(vec) = mutex.as_mut() {
+            vec.push((task_id, task_status));
+        } else {
+            *mutex = Some(vec![(task_id, task_status)]);
+        }
+    }
+
     pub(crate) async fn update_task_status_by_id(
         &mut self,
         task_id: usize,
         task_status: TaskStatus,
     ) -> bool {
-        if let Some(map) = self.id_task_mapping.as_ref() {
-            if map.contains_key(&task_id) {
-                match task_status {
-                    TaskStatus::Pause => {
-                        if let Some(idle_task) = self.idle_task.as_ref() {
-                            if idle_task.contains_key(&task_id) {
-                                return true;
-                            }
-                        }
-                        if let Some(sender) = map.get(&task_id) {
-                            let (tx, mut rx) = channel(1);
-                            if let Ok(()) = sender.send(TaskSignal::Pause(tx)).await {
-                                if let Some(task_actor) = rx.recv().await {
-                                    if let Some(idle_task) = self.idle_task.as_mut() {
-                                        idle_task.insert(task_id, task_actor);
-                                    } else {
-                                        let mut map = HashMap::new();
-                                        map.insert(task_id, task_actor);
-                                        self.idle_task = Some(map);
-                                    }
-                                    let mut mutex = self.notice_list.write().await;
-                                    if let Some(vec) = mutex.as_mut() {
-                                        vec.push((task_id, task_status));
-                                    } else {
-                                        *mutex = Some(vec![(task_id, task_status)]);
-                                    }
-                                    return true;
-                                }
-                            }
-                        }
-                    }
-                    TaskStatus::Destory => {
-                        if let Some(sender) = map.get(&task_id) {
-                            if let Ok(()) = sender.send(TaskSignal::Destory).await {
-                                let mut mutex = self.notice_list.write().await;
-                                if let Some(vec) = mutex.as_mut() {
-                                    vec.push((task_id, task_status));
-                                } else {
-                                    *mutex = Some(vec![(task_id, task_status)]);
-                                }
-                                return true;
-                            }
-                        }
-                    }
-                    TaskStatus::Running => {
-                        if let Some(idle_task) = self.idle_task.as_mut() {
-                            if idle_task.contains_key(&task_id) {
-                                if let Some(task_actor) = idle_task.remove(&task_id) {
-                                    task_actor.start_actor();
-                                    let mut mutex = self.notice_list.write().await;
-                                    if let Some(vec) = mutex.as_mut() {
-                                        vec.push((task_id, task_status));
-                                    } else {
-                                        *mutex = Some(vec![(task_id, task_status)]);
-                                    }
-                                    return true;
-                                }
-                            }
-                        }
+        self.remove_completed_tasks();
+        let Some(sender) = self
+            .id_task_mapping
+            .as_ref()
+            .and_then(|map| map.get(&task_id))
+        else {
+            return false;
+        };
+        match task_status {
+            TaskStatus::Pause => {
+                if let Some(idle_task) = self.idle_task.as_ref()
+                    && idle_task.contains_key(&task_id)
+                {
+                    return true;
+                }
+                let (tx, mut rx) = channel(1);
+                if let Ok(()) = sender.send(TaskSignal::Pause(tx)).await
+                    && let Some(task_actor) = rx.recv().await
+                {
+                    self.idle_task
+                        .get_or_insert_with(HashMap::new)
+                        .insert(task_id, task_actor);
+                    self.push_notice(task_id, task_status).await;
+                    self.events.send(SchedulerEvent::Paused { task_id });
+                    return true;
+                }
+            }
+            TaskStatus::Destory => {
+                if let Ok(()) = sender.send(TaskSignal::Destory).await {
+                    self.push_notice(task_id, task_status).await;
+                    self.remove_task(task_id);
+                    self.events.send(SchedulerEvent::Destroyed { task_id });
+                    return true;
+                }
+            }
+            TaskStatus::Running => {
+                if let Some(mut task_actor) = self
+                    .idle_task
+                    .as_mut()
+                    .and_then(|idle_task| idle_task.remove(&task_id))
+                {
+                    task_actor.reset_target_date_time();
+                    // 暂停期间超出了结束时间，时间轮中的任务随之丢弃
+                    if task_actor.is_completed() {
+                        self.push_notice(task_id, TaskStatus::Completed).await;
+                        self.remove_task(task_id);
+                        return false;
                     }
+                    task_actor.start_actor();
+                    self.push_notice(task_id, task_status).await;
+                    self.events.send(SchedulerEvent::Resumed { task_id });
+                    return true;
                 }
             }
+            TaskStatus::Completed => {}
         }
         false
     }
 
+    /// 暂停中的任务直接更新TaskActor，避免信号在暂停期间堆积
     pub(crate) async fn update_cron_by_id(&mut self, task_id: usize, cron: Schedule) -> bool {
-        if let Some(id_task_mapping) = self.id_task_mapping.as_ref() {
-            if id_task_mapping.contains_key(&task_id) {
-                if let Some(sender) = id_task_mapping.get(&task_id) {
-                    if let Ok(()) = sender.send(TaskSignal::UpdateCron(cron)).await {
-                        return true;
-                    }
-                }
-            }
+        self.remove_completed_tasks();
+        let cron_str = cron.to_string();
+        if let Some(task_actor) = self
+            .idle_task
+            .as_mut()
+            .and_then(|idle_task| idle_task.get_mut(&task_id))
+        {
+            task_actor.update_cron(Box::new(cron));
+            self.events.send(SchedulerEvent::CronUpdated {
+                task_id,
+                cron: cron_str,
+            });
+            return true;
+        }
+        if let Some(sender) = self
+            .id_task_mapping
+            .as_ref()
+            .and_then(|map| map.get(&task_id))
+            && let Ok(()) = sender.send(TaskSignal::UpdateCron(Box::new(cron))).await
+        {
+            self.events.send(SchedulerEvent::CronUpdated {
+                task_id,
+                cron: cron_str,
+            });
+            return true;
         }
         false
     }
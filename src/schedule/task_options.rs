@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::schedule::{LimitBehavior, MisfirePolicy, OverlapPolicy, RetryPolicy, TaskTimeZone};
+
+/// ## 任务选项
+/// 配合`push_order_task_with_options`/`push_disorder_task_with_options`使用
+#[derive(Clone, Debug, Default)]
+pub struct TaskOptions {
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) time_zone: Option<TaskTimeZone>,
+    pub(crate) name: Option<String>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) overlap_policy: Option<OverlapPolicy>,
+    pub(crate) max_concurrent: Option<(usize, LimitBehavior)>,
+    pub(crate) starting_at: Option<DateTime<Local>>,
+    pub(crate) misfire_policy: Option<MisfirePolicy>,
+    pub(crate) misfire_threshold: Option<Duration>,
+    /// 任务函数在`HandlerRegistry`中的名称，只有通过注册表新增的任务才有
+    pub(crate) handler: Option<String>,
+}
+
+impl TaskOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置失败重试策略
+    pub fn retry(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// 设置单次运行的超时时间，超时后本次运行被取消并记为超时，不影响下一次运行
+    /// 未设置时使用`Scheduler::set_default_timeout`设置的默认超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 设置解析cron表达式所用的时区
+    /// 未设置时使用`Scheduler::set_default_time_zone`设置的默认时区（默认为系统本地时区）
+    pub fn time_zone(mut self, time_zone: impl Into<TaskTimeZone>) -> Self {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+
+    /// 设置任务名称，同一调度器中的任务名称不能重复，可以通过`Scheduler::task_id_by_name`查询任务id
+    /// 未设置时任务名称为任务函数的类型名
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 添加一个标签，可以通过`Scheduler::pause_by_tag`等方法批量操作带有该标签的任务
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
+    /// 添加多个标签
+    pub fn tags(self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        tags.into_iter().fold(self, |options, tag| options.tag(tag))
+    }
+
+    /// 设置触发时上一次运行尚未结束的处理方式，设置后不再区分有序/无序任务
+    /// 未设置时`push_order_task`为`OverlapPolicy::Queue`，`push_disorder_task`为`OverlapPolicy::Parallel`
+    pub fn overlap_policy(mut self, overlap_policy: OverlapPolicy) -> Self {
+        self.overlap_policy = Some(overlap_policy);
+        self
+    }
+
+    /// 设置同时运行的最大数量（最小为1），用于限制运行时间长于cron间隔的无序任务的并发数量
+    /// 达到上限时按`behavior`等待、放弃或合并本次运行，未设置时不限制
+    pub fn max_concurrent(mut self, max_concurrent: usize, behavior: LimitBehavior) -> Self {
+        self.max_concurrent = Some((max_concurrent, behavior));
+        self
+    }
+
+    /// 设置生效时间，任务从该时间之后的第一个触发时间开始触发，暂停后恢复时同样不早于该时间
+    /// 配合`RepeatModel::Until`可以让任务只在一段时间内触发
+    pub fn starting_at(mut self, starting_at: DateTime<Local>) -> Self {
+        self.starting_at = Some(starting_at);
+        self
+    }
+
+    /// 设置错过触发的处理方式，未设置时为`MisfirePolicy::FireOnce`
+    pub fn misfire_policy(mut self, misfire_policy: MisfirePolicy) -> Self {
+        self.misfire_policy = Some(misfire_policy);
+        self
+    }
+
+    /// 设置错过触发阈值，实际触发时间晚于计划时间超过该值时按`MisfirePolicy`处理
+    /// 未设置时使用`SchedulerBuilder::misfire_threshold`设置的默认阈值（默认为1秒）
+    pub fn misfire_threshold(mut self, misfire_threshold: Duration) -> Self {
+        self.misfire_threshold = Some(misfire_threshold);
+        self
+    }
+}
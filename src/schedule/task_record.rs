@@ -0,0 +1,180 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Local};
+use tokio::sync::watch;
+
+use crate::schedule::{
+    LimitBehavior, MisfirePolicy, OverlapPolicy, RepeatModel, RetryPolicy, Schedule, TaskInfo,
+    TaskOptions, TaskSnapshot, TaskStats, TaskTimeZone, concurrency_limit::ConcurrencyLimit,
+    task_actor::TaskStatus,
+};
+
+/// 任务记录的共享引用，由TaskManager、TaskActor以及每次运行共同持有
+pub(crate) type SharedTaskRecord = Arc<Mutex<TaskRecord>>;
+
+/// ## 任务记录
+/// 保存无需与TaskActor通信即可读取的任务状态
+pub(crate) struct TaskRecord {
+    /// 任务状态
+    pub(crate) status: TaskStatus,
+    /// 运行统计
+    pub(crate) stats: TaskStats,
+    /// 任务名称，默认为任务函数的类型名
+    pub(crate) name: String,
+    /// 名称是否通过`TaskOptions::name`设置，只有设置的名称才保存到快照
+    pub(crate) named: bool,
+    pub(crate) tags: Vec<String>,
+    /// 触发计划，由TaskActor在更新cron时同步
+    pub(crate) schedule: Schedule,
+    pub(crate) time_zone: TaskTimeZone,
+    pub(crate) overlap_policy: OverlapPolicy,
+    /// 已触发尚未结束的运行次数，包括排队中与正在运行的运行
+    pub(crate) running: usize,
+    /// 取消运行时加一，触发时订阅，之后值变化则取消运行
+    pub(crate) cancel: watch::Sender<u64>,
+    /// 并发上限，未设置时不限制
+    pub(crate) concurrency_limit: Option<ConcurrencyLimit>,
+    /// 剩余运行次数，`None`表示不限
+    pub(crate) repeat_remaining: Option<usize>,
+    /// 任务函数在`HandlerRegistry`中的名称，有名称的任务才能保存快照
+    pub(crate) handler: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) max_concurrent: Option<(usize, LimitBehavior)>,
+    pub(crate) misfire_policy: MisfirePolicy,
+    pub(crate) misfire_threshold: Option<Duration>,
+    /// 生效时间与结束时间
+    pub(crate) starting_at: Option<DateTime<Local>>,
+    pub(crate) until: Option<DateTime<Local>>,
+    /// 下一次触发时间，由TaskActor在设置目标时间时同步，不再触发时为`None`
+    pub(crate) next_fire: Option<DateTime<Local>>,
+    /// 最近一次开始运行的时间
+    pub(crate) last_fire: Option<DateTime<Local>>,
+}
+
+impl TaskRecord {
+    /// 未通过`TaskOptions::name`设置名称时使用`default_name`
+    pub(crate) fn new_shared(
+        default_name: &str,
+        schedule: Schedule,
+        options: &TaskOptions,
+        repeat_model: &RepeatModel,
+        next_fire: DateTime<Local>,
+    ) -> SharedTaskRecord {
+        Arc::new(Mutex::new(Self {
+            status: TaskStatus::Running,
+            stats: TaskStats::default(),
+            name: options
+                .name
+                .clone()
+                .unwrap_or_else(|| default_name.to_string()),
+            named: options.name.is_some(),
+            tags: options.tags.clone(),
+            schedule,
+            time_zone: options.time_zone.unwrap_or_default(),
+            overlap_policy: options.overlap_policy.unwrap_or_default(),
+            running: 0,
+            cancel: watch::Sender::new(0),
+            concurrency_limit: options
+                .max_concurrent
+                .map(|(max_concurrent, behavior)| ConcurrencyLimit::new(max_concurrent, behavior)),
+            repeat_remaining: repeat_model.remaining(),
+            handler: options.handler.clone(),
+            timeout: options.timeout,
+            retry_policy: options.retry_policy.clone(),
+            max_concurrent: options.max_concurrent,
+            misfire_policy: options.misfire_policy.unwrap_or_default(),
+            misfire_threshold: options.misfire_threshold,
+            starting_at: options.starting_at,
+            until: repeat_model.until(),
+            next_fire: Some(next_fire),
+            last_fire: None,
+        }))
+    }
+
+    pub(crate) fn info(&self, task_id: usize) -> TaskInfo {
+        TaskInfo {
+            id: task_id,
+            name: self.name.clone(),
+            tags: self.tags.clone(),
+            cron: self.schedule.to_string(),
+            order_type: self.overlap_policy.order_type(),
+            overlap_policy: self.overlap_policy,
+            status: self.status.clone(),
+            repeat_remaining: self.repeat_remaining,
+            next_fire: self.next_fire,
+            last_fire: self.last_fire,
+            last_result: self.stats.last_result.clone(),
+        }
+    }
+
+    /// 通过注册表新增的运行中或暂停中的任务才能保存快照
+    pub(crate) fn snapshot(&self) -> Option<TaskSnapshot> {
+        if !matches!(self.status, TaskStatus::Running | TaskStatus::Pause) {
+            return None;
+        }
+        let repeat = match (self.until, self.repeat_remaining) {
+            (Some(until), _) => RepeatModel::Until(until),
+            (None, Some(remaining)) => RepeatModel::Times(remaining),
+            (None, None) => RepeatModel::Repetition,
+        };
+        Some(TaskSnapshot {
+            name: self.named.then(|| self.name.clone()),
+            handler: self.handler.clone()?,
+            schedule: self.schedule.clone(),
+            repeat,
+            overlap_policy: self.overlap_policy,
+            tags: self.tags.clone(),
+            time_zone: self.time_zone,
+            timeout: self.timeout,
+            retry_policy: self.retry_policy.clone(),
+            max_concurrent: self.max_concurrent,
+            starting_at: self.starting_at,
+            misfire_policy: self.misfire_policy,
+            misfire_threshold: self.misfire_threshold,
+            status: self.status.clone(),
+            next_fire: self.next_fire,
+            last_fire: self.last_fire,
+        })
+    }
+
+    /// 取消所有已触发尚未结束的运行
+    pub(crate) fn cancel_running(&self) {
+        self.cancel.send_modify(|generation| *generation += 1);
+    }
+
+    /// 从下一次触发时间开始计算之后的`n`次触发时间，不超过剩余运行次数与结束时间
+    /// 暂停中的任务恢复时会重新计算目标时间，因此从`now`开始计算
+    /// `FixedDelay`的触发时间取决于运行结束的时间，只返回下一次触发时间
+    pub(crate) fn upcoming(&self, n: usize, now: DateTime<Local>) -> Vec<DateTime<Local>> {
+        let n = self
+            .repeat_remaining
+            .map_or(n, |remaining| remaining.min(n));
+        let first = match self.status {
+            TaskStatus::Running => self.next_fire,
+            TaskStatus::Pause if !self.schedule.is_one_shot() => {
+                let after = self
+                    .starting_at
+                    .map_or(now, |starting_at| now.max(starting_at));
+                self.schedule.first_after(&self.time_zone, &after)
+            }
+            TaskStatus::Pause => self.next_fire,
+            TaskStatus::Destory | TaskStatus::Completed => None,
+        };
+        let mut upcoming = Vec::with_capacity(n);
+        let mut next = first;
+        while let Some(datetime) = next
+            && upcoming.len() < n
+            && self.until.is_none_or(|until| datetime <= until)
+        {
+            upcoming.push(datetime);
+            next = self
+                .schedule
+                .next_after(&self.time_zone, &datetime, &datetime);
+        }
+        upcoming
+    }
+}
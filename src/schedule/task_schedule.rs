@@ -0,0 +1,158 @@
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use chrono::{DateTime, Local, TimeDelta};
+
+use crate::schedule::TaskTimeZone;
+
+/// ## 任务的触发计划
+/// 配合`Scheduler::push_task_with_schedule`使用，`push_task`等方法传入的cron表达式等同于`Schedule::Cron`
+///
+/// - `Every`：固定频率，从新增任务时开始每隔一段时间触发一次，触发时间与第一次触发时间对齐，错过的时间点按`MisfirePolicy`处理
+/// - `FixedDelay`：固定延迟，上一次运行结束后再隔一段时间触发，同一任务不会同时运行
+/// - `After`：新增任务后隔一段时间触发一次
+/// - `At`：在指定时间触发一次，指定时间已经过去时立即触发
+///
+/// `After`与`At`只触发一次，与`RepeatModel`无关；只有`Cron`使用任务的时区
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum Schedule {
+    /// cron表达式
+    Cron(#[cfg_attr(feature = "persistence", serde(with = "cron_expression"))] Box<cron::Schedule>),
+    /// 固定频率
+    Every(Duration),
+    /// 固定延迟，从上一次运行结束时开始计算
+    FixedDelay(Duration),
+    /// 延迟触发一次
+    After(Duration),
+    /// 在指定时间触发一次
+    At(DateTime<Local>),
+}
+
+impl Schedule {
+    /// 预览`after`之后最多`n`次的触发时间，不考虑`RepeatModel`
+    /// `FixedDelay`之后的触发时间取决于每次运行结束的时间，只返回第一次触发时间
+    pub fn upcoming(
+        &self,
+        time_zone: &TaskTimeZone,
+        after: &DateTime<Local>,
+        n: usize,
+    ) -> Vec<DateTime<Local>> {
+        let mut upcoming = Vec::with_capacity(n);
+        let mut next = self.first_after(time_zone, after);
+        while let Some(datetime) = next
+            && upcoming.len() < n
+        {
+            upcoming.push(datetime);
+            next = self.next_after(time_zone, &datetime, &datetime);
+        }
+        upcoming
+    }
+
+    /// 新增任务或恢复任务时的第一次触发时间
+    pub(crate) fn first_after(
+        &self,
+        time_zone: &TaskTimeZone,
+        now: &DateTime<Local>,
+    ) -> Option<DateTime<Local>> {
+        match self {
+            Schedule::Cron(cron_schedule) => time_zone.next_after(cron_schedule, now),
+            Schedule::Every(duration)
+            | Schedule::FixedDelay(duration)
+            | Schedule::After(duration) => now.checked_add_signed(to_time_delta(*duration)?),
+            Schedule::At(datetime) => Some(*datetime),
+        }
+    }
+
+    /// 本次目标时间为`previous`时，`after`之后的下一次触发时间
+    /// `FixedDelay`的下一次触发时间在运行结束后才能确定，此处返回`None`
+    pub(crate) fn next_after(
+        &self,
+        time_zone: &TaskTimeZone,
+        after: &DateTime<Local>,
+        previous: &DateTime<Local>,
+    ) -> Option<DateTime<Local>> {
+        match self {
+            Schedule::Cron(cron_schedule) => time_zone.next_after(cron_schedule, after),
+            Schedule::Every(duration) => {
+                let interval = to_time_delta(*duration)?;
+                let elapsed = after.signed_duration_since(*previous);
+                // 跳过已经错过的时间点，保持与第一次触发时间对齐
+                let times = (elapsed.num_milliseconds() / interval.num_milliseconds()).max(0) + 1;
+                previous.checked_add_signed(interval.checked_mul(times.try_into().ok()?)?)
+            }
+            Schedule::FixedDelay(_) | Schedule::After(_) | Schedule::At(_) => None,
+        }
+    }
+
+    /// 固定延迟的间隔
+    pub(crate) fn fixed_delay(&self) -> Option<Duration> {
+        match self {
+            Schedule::FixedDelay(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+
+    /// 只触发一次的计划，恢复运行时保留原有的目标时间
+    pub(crate) fn is_one_shot(&self) -> bool {
+        matches!(self, Schedule::After(_) | Schedule::At(_))
+    }
+}
+
+/// 间隔至少为1毫秒
+fn to_time_delta(duration: Duration) -> Option<TimeDelta> {
+    TimeDelta::from_std(duration)
+        .ok()
+        .map(|time_delta| time_delta.max(TimeDelta::milliseconds(1)))
+}
+
+impl From<cron::Schedule> for Schedule {
+    fn from(cron_schedule: cron::Schedule) -> Self {
+        Schedule::Cron(Box::new(cron_schedule))
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = cron::error::Error;
+
+    /// 解析cron表达式
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        cron::Schedule::from_str(s).map(Schedule::from)
+    }
+}
+
+impl Display for Schedule {
+    /// `Cron`输出cron表达式，其余输出如`every 5s`、`fixed delay 5s`、`after 10s`、`at 2025-01-01 09:00:00 +08:00`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Schedule::Cron(cron_schedule) => write!(f, "{cron_schedule}"),
+            Schedule::Every(duration) => write!(f, "every {duration:?}"),
+            Schedule::FixedDelay(duration) => write!(f, "fixed delay {duration:?}"),
+            Schedule::After(duration) => write!(f, "after {duration:?}"),
+            Schedule::At(datetime) => write!(f, "at {datetime}"),
+        }
+    }
+}
+
+/// cron表达式按字符串保存
+#[cfg(feature = "persistence")]
+mod cron_expression {
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub(super) fn serialize<S: Serializer>(
+        cron_schedule: &cron::Schedule,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(cron_schedule)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<cron::Schedule>, D::Error> {
+        let cron = String::deserialize(deserializer)?;
+        cron::Schedule::from_str(&cron)
+            .map(Box::new)
+            .map_err(D::Error::custom)
+    }
+}
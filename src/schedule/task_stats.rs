@@ -0,0 +1,108 @@
+use std::{fmt::Display, time::Duration};
+
+use chrono::{DateTime, Local};
+
+/// ## 单次运行结果
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskRunResult {
+    /// 运行成功
+    Success,
+    /// 运行失败，包含错误信息
+    Failed(String),
+    /// 运行超时，超过设置的时间后本次运行被取消
+    TimedOut(Duration),
+    /// 运行被新的触发取消（`OverlapPolicy::Replace`），不计为失败
+    Cancelled,
+}
+
+impl TaskRunResult {
+    pub(crate) fn from_result(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => TaskRunResult::Success,
+            Err(error) => TaskRunResult::Failed(error),
+        }
+    }
+
+    /// 是否为失败或超时
+    pub fn is_failure(&self) -> bool {
+        matches!(self, TaskRunResult::Failed(_) | TaskRunResult::TimedOut(_))
+    }
+
+    /// 失败或超时的错误信息
+    pub fn error_message(&self) -> Option<String> {
+        match self {
+            TaskRunResult::Success | TaskRunResult::Cancelled => None,
+            TaskRunResult::Failed(error) => Some(error.clone()),
+            TaskRunResult::TimedOut(timeout) => Some(format!("task timed out after {timeout:?}")),
+        }
+    }
+}
+
+/// ## 任务运行统计
+#[derive(Clone, Debug, Default)]
+pub struct TaskStats {
+    /// 运行次数
+    pub run_count: usize,
+    /// 成功次数
+    pub success_count: usize,
+    /// 失败次数，包含超时
+    pub failure_count: usize,
+    /// 超时次数
+    pub timeout_count: usize,
+    /// 被取消的次数
+    pub cancelled_count: usize,
+    /// 连续失败次数，成功后清零
+    pub consecutive_failures: usize,
+    /// 最近一次运行结果
+    pub last_result: Option<TaskRunResult>,
+    /// 最近一次失败的错误信息
+    pub last_error: Option<String>,
+    /// 最近一次失败的时间
+    pub last_error_time: Option<DateTime<Local>>,
+}
+
+impl TaskStats {
+    pub(crate) fn record(&mut self, result: TaskRunResult, end_time: DateTime<Local>) {
+        self.run_count += 1;
+        if let Some(error) = result.error_message() {
+            self.failure_count += 1;
+            self.consecutive_failures += 1;
+            if let TaskRunResult::TimedOut(_) = result {
+                self.timeout_count += 1;
+            }
+            self.last_error = Some(error);
+            self.last_error_time = Some(end_time);
+        } else if let TaskRunResult::Cancelled = result {
+            self.cancelled_count += 1;
+        } else {
+            self.success_count += 1;
+            self.consecutive_failures = 0;
+        }
+        self.last_result = Some(result);
+    }
+
+    /// 最近一次运行是否失败或超时
+    pub fn is_failing(&self) -> bool {
+        self.last_result
+            .as_ref()
+            .is_some_and(|result| result.is_failure())
+    }
+}
+
+/// ## 任务函数返回值
+/// 任务函数可以返回`()`，也可以返回`Result<(), E>`，其中`E`需要实现`Display`
+pub trait IntoTaskResult: Send + 'static {
+    fn into_task_result(self) -> Result<(), String>;
+}
+
+impl IntoTaskResult for () {
+    fn into_task_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: Display + Send + 'static> IntoTaskResult for Result<(), E> {
+    fn into_task_result(self) -> Result<(), String> {
+        self.map_err(|error| error.to_string())
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::schedule::{
+    LimitBehavior, MisfirePolicy, OverlapPolicy, RepeatModel, RetryPolicy, Schedule, TaskStatus,
+    TaskTimeZone,
+};
+
+/// ## 任务快照
+/// 通过`Scheduler::snapshot`获取，只包含通过`push_registered_task`新增（任务函数可以按名称找到）的运行中与暂停中的任务
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskSnapshot {
+    /// 通过`TaskOptions::name`设置的任务名称
+    pub name: Option<String>,
+    /// 任务函数在`HandlerRegistry`中的名称
+    pub handler: String,
+    pub schedule: Schedule,
+    /// 剩余的重复方式，`Times`为剩余运行次数
+    pub repeat: RepeatModel,
+    pub overlap_policy: OverlapPolicy,
+    pub tags: Vec<String>,
+    pub time_zone: TaskTimeZone,
+    pub timeout: Option<Duration>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub max_concurrent: Option<(usize, LimitBehavior)>,
+    pub starting_at: Option<DateTime<Local>>,
+    #[cfg_attr(feature = "persistence", serde(default))]
+    pub misfire_policy: MisfirePolicy,
+    #[cfg_attr(feature = "persistence", serde(default))]
+    pub misfire_threshold: Option<Duration>,
+    /// 运行中或暂停中
+    pub status: TaskStatus,
+    pub next_fire: Option<DateTime<Local>>,
+    pub last_fire: Option<DateTime<Local>>,
+}
+
+/// ## 任务存储
+/// 保存与读取任务快照，配合`Scheduler::save`与`Scheduler::restore`使用
+///
+/// 开启`persistence` feature后可以使用基于JSON文件的`JsonFileStore`
+pub trait TaskStore {
+    /// 保存全部任务快照，覆盖之前保存的快照
+    fn save(&self, snapshots: &[TaskSnapshot]) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// 读取最近一次保存的任务快照，从未保存时返回空列表
+    fn load(&self) -> Result<Vec<TaskSnapshot>, Box<dyn std::error::Error>>;
+}
+
+#[cfg(feature = "persistence")]
+pub use json_file_store::JsonFileStore;
+
+#[cfg(feature = "persistence")]
+mod json_file_store {
+    use std::{
+        fs,
+        io::ErrorKind,
+        path::{Path, PathBuf},
+    };
+
+    use crate::schedule::{TaskSnapshot, TaskStore};
+
+    /// ## JSON文件存储
+    /// 先写入同目录下的临时文件再替换，保存过程中进程退出不会损坏之前保存的快照
+    #[derive(Clone, Debug)]
+    pub struct JsonFileStore {
+        path: PathBuf,
+    }
+
+    impl JsonFileStore {
+        pub fn new(path: impl AsRef<Path>) -> Self {
+            Self {
+                path: path.as_ref().to_path_buf(),
+            }
+        }
+
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl TaskStore for JsonFileStore {
+        fn save(&self, snapshots: &[TaskSnapshot]) -> Result<(), Box<dyn std::error::Error>> {
+            let json = serde_json::to_string_pretty(snapshots)?;
+            let mut temp_path = self.path.clone().into_os_string();
+            temp_path.push(".tmp");
+            fs::write(&temp_path, json)?;
+            fs::rename(&temp_path, &self.path)?;
+            Ok(())
+        }
+
+        fn load(&self) -> Result<Vec<TaskSnapshot>, Box<dyn std::error::Error>> {
+            match fs::read_to_string(&self.path) {
+                Ok(json) => Ok(serde_json::from_str(&json)?),
+                Err(error) if error.kind() == ErrorKind::NotFound => Ok(vec![]),
+                Err(error) => Err(Box::new(error)),
+            }
+        }
+    }
+}
@@ -1,239 +1,442 @@
 use std::{
-    collections::VecDeque,
-    sync::{Arc, Mutex},
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
     vec,
 };
 
-use chrono::Local;
-use tokio::sync::RwLock;
+use chrono::{DateTime, Local, TimeDelta};
 
+#[cfg(feature = "metrics")]
+use crate::schedule::WheelOccupancy;
 use crate::schedule::{
-    config::{
-        DEFAULT_HOUR_TIME_WHEEL_SETTING, DEFAULT_MILLISECOND_TIME_WHEEL_SETTING,
-        DEFAULT_MINUTE_TIME_WHEEL_SETTING, DEFAULT_SECOND_TIME_WHEEL_SETTING,
-    },
-    task_actor::{ITaskHandler, Task, TaskPollTrait, TaskSignal, TaskStatus},
+    MisfirePolicy, SchedulerEvent, TaskContext,
+    clock::SharedClock,
+    config::{DEFAULT_PAUSE_CHECK_TIME, SchedulerConfig},
+    scheduler_event::EventSender,
+    shutdown::SharedLifecycle,
+    task_actor::{Task, TaskOrderType, TaskPollTrait, TaskRun, TaskSignal, TaskStatus},
+    task_manager::NoticeList,
 };
 
 /// ## 多层时间轮
-/// 分4层，分别是：毫秒级、秒级、分钟级、小时级
-/// 时间轮每25毫秒tick一次
+/// 分4层，分别是：毫秒级、秒级、分钟级、小时级，每层的槽位数量与间隔由`SchedulerConfig`设置
+/// 时间轮默认每25毫秒tick一次，当前时间由注入的时钟提供
+///
+/// 超出小时时间轮范围的任务（如每周、每月、每年的任务）按目标时间放入溢出队列，进入小时时间轮的范围后再放入时间轮
 pub(crate) struct TierTimeWheel {
     millisecond_time_wheel: *mut TimeWheel,
     second_time_wheel: *mut TimeWheel,
     minute_time_wheel: *mut TimeWheel,
     hour_time_wheel: *mut TimeWheel,
+    overflow_queue: *mut BTreeMap<DateTime<Local>, Vec<Task>>,
     mutex: Mutex<()>,
+    clock: SharedClock,
+    events: EventSender,
+    lifecycle: SharedLifecycle,
+    /// 自身的弱引用，交给固定延迟任务的运行，运行结束后将任务放回时间轮
+    weak_self: Weak<TierTimeWheel>,
+    /// 最低一层时间轮的槽位间隔，距离目标时间不超过该值的任务即可触发
+    precision: i64,
+    /// 默认的错过触发阈值（毫秒）
+    misfire_threshold: i64,
 }
 
 unsafe impl Send for TierTimeWheel {}
 unsafe impl Sync for TierTimeWheel {}
 
 impl TierTimeWheel {
-    pub(crate) fn new() -> Self {
-        Self {
+    pub(crate) fn new_shared(
+        clock: SharedClock,
+        events: EventSender,
+        lifecycle: SharedLifecycle,
+        config: &SchedulerConfig,
+    ) -> Arc<Self> {
+        let [millisecond, second, minute, hour] = config.time_wheel_settings;
+        Arc::new_cyclic(|weak_self| Self {
             millisecond_time_wheel: Box::into_raw(Box::new(TimeWheel::new(
-                DEFAULT_MILLISECOND_TIME_WHEEL_SETTING.0,
-                DEFAULT_MILLISECOND_TIME_WHEEL_SETTING.1,
-            ))),
-            second_time_wheel: Box::into_raw(Box::new(TimeWheel::new(
-                DEFAULT_SECOND_TIME_WHEEL_SETTING.0,
-                DEFAULT_SECOND_TIME_WHEEL_SETTING.1,
-            ))),
-            minute_time_wheel: Box::into_raw(Box::new(TimeWheel::new(
-                DEFAULT_MINUTE_TIME_WHEEL_SETTING.0,
-                DEFAULT_MINUTE_TIME_WHEEL_SETTING.1,
-            ))),
-            hour_time_wheel: Box::into_raw(Box::new(TimeWheel::new(
-                DEFAULT_HOUR_TIME_WHEEL_SETTING.0,
-                DEFAULT_HOUR_TIME_WHEEL_SETTING.1,
+                millisecond.0,
+                millisecond.1,
             ))),
+            second_time_wheel: Box::into_raw(Box::new(TimeWheel::new(second.0, second.1))),
+            minute_time_wheel: Box::into_raw(Box::new(TimeWheel::new(minute.0, minute.1))),
+            hour_time_wheel: Box::into_raw(Box::new(TimeWheel::new(hour.0, hour.1))),
+            overflow_queue: Box::into_raw(Box::new(BTreeMap::new())),
             mutex: Mutex::new(()),
+            clock,
+            events,
+            lifecycle,
+            weak_self: weak_self.clone(),
+            precision: millisecond.1 as i64,
+            misfire_threshold: config.misfire_threshold as i64,
+        })
+    }
+
+    pub(crate) fn get_clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
+    pub(crate) fn now(&self) -> DateTime<Local> {
+        self.clock.now()
+    }
+
+    pub(crate) fn get_events(&self) -> EventSender {
+        self.events.clone()
+    }
+
+    pub(crate) fn get_lifecycle(&self) -> SharedLifecycle {
+        self.lifecycle.clone()
+    }
+
+    /// 发送任务触发事件
+    fn send_fired_event(&self, task_id: usize, scheduled: DateTime<Local>) {
+        self.events.send(SchedulerEvent::Fired {
+            task_id,
+            scheduled,
+            actual: self.now(),
+        });
+    }
+
+    /// 晚于计划时间超过阈值时发送错过触发事件，返回是否错过触发
+    /// 未单独设置阈值的任务使用调度器的默认阈值
+    fn check_misfire(
+        &self,
+        task_id: usize,
+        scheduled: DateTime<Local>,
+        threshold: Option<Duration>,
+        policy: MisfirePolicy,
+    ) -> bool {
+        let actual = self.now();
+        let threshold = threshold.map_or(self.misfire_threshold, |threshold| {
+            threshold.as_millis() as i64
+        });
+        if actual.signed_duration_since(scheduled).num_milliseconds() <= threshold {
+            return false;
         }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(task_id, %scheduled, %actual, ?policy, "task misfired");
+        self.events.send(SchedulerEvent::Misfired {
+            task_id,
+            scheduled,
+            actual,
+            policy,
+        });
+        true
     }
 
+    /// 将任务放入能容纳`milliseconds`的最低一层时间轮，所有时间轮都无法容纳时放入溢出队列
+    /// 任务所在槽位被检查时不会晚于目标时间，之后再按剩余时间降级到更低一层
     pub(crate) fn push_T_to_time_wheel(&self, task: Task, milliseconds: i64) {
-        if milliseconds > 1000 {
-            let seconds = (milliseconds.abs() / 1000) as u64;
-            if seconds > 60 * 60 {
-                // 小时级
-                self.push_T_to_hour_time_wheel(task, seconds);
-            } else if seconds > 60 {
-                // 分钟级
-                self.push_T_to_minute_time_wheel(task, seconds);
-            } else {
-                // 秒级
-                self.push_T_to_second_time_wheel(task, seconds);
-            }
-        } else {
-            // 毫秒级
-            self.push_T_to_millisecond_time_wheel(task, milliseconds as u64);
+        if let Ok(_mutex) = self.mutex.lock() {
+            self.push_T_without_lock(task, milliseconds.max(0) as u64);
         }
     }
 
-    fn push_T_to_millisecond_time_wheel(&self, task: Task, milliseconds: u64) {
+    /// 将`layer`层中尚未到期的任务按剩余时间降级
+    fn demote_T(&self, task: Task, milliseconds: i64, layer: &'static str) {
+        #[cfg(feature = "tracing")]
+        let task_id = task.get_id();
         if let Ok(_mutex) = self.mutex.lock() {
-            if let Some(millisecond_time_wheel) = unsafe { self.millisecond_time_wheel.as_mut() } {
-                let mut target_pointer =
-                    millisecond_time_wheel.pointer + (milliseconds / 100) as usize;
-                target_pointer = target_pointer % millisecond_time_wheel.slot.len();
-                let _ = &millisecond_time_wheel.slot[target_pointer as usize].push_back(task);
-            }
+            let _target_layer = self.push_T_without_lock(task, milliseconds.max(0) as u64);
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                task_id,
+                from = layer,
+                to = _target_layer,
+                remaining_ms = milliseconds,
+                "task demoted"
+            );
         }
     }
 
-    fn push_T_to_second_time_wheel(&self, task: Task, seconds: u64) {
+    /// 各层时间轮及其名称，从低到高排列
+    fn time_wheels(&self) -> [(&'static str, *mut TimeWheel); 4] {
+        [
+            ("millisecond", self.millisecond_time_wheel),
+            ("second", self.second_time_wheel),
+            ("minute", self.minute_time_wheel),
+            ("hour", self.hour_time_wheel),
+        ]
+    }
+
+    /// 各层时间轮与溢出队列中的任务数量
+    #[cfg(feature = "metrics")]
+    pub(crate) fn get_occupancy(&self) -> WheelOccupancy {
+        let mut occupancy = WheelOccupancy::default();
         if let Ok(_mutex) = self.mutex.lock() {
-            if let Some(second_time_wheel) = unsafe { self.second_time_wheel.as_mut() } {
-                let mut target_pointer = second_time_wheel.pointer + seconds as usize;
-                target_pointer = target_pointer % second_time_wheel.slot.len();
-                let _ = &second_time_wheel.slot[target_pointer as usize].push_back(task);
-            }
+            let count = |time_wheel: *mut TimeWheel| {
+                unsafe { time_wheel.as_ref() }.map_or(0, |time_wheel| time_wheel.len())
+            };
+            occupancy.millisecond = count(self.millisecond_time_wheel);
+            occupancy.second = count(self.second_time_wheel);
+            occupancy.minute = count(self.minute_time_wheel);
+            occupancy.hour = count(self.hour_time_wheel);
+            occupancy.overflow = unsafe { self.overflow_queue.as_ref() }
+                .map_or(0, |overflow_queue| {
+                    overflow_queue.values().map(Vec::len).sum()
+                });
         }
+        occupancy
     }
 
-    fn push_T_to_minute_time_wheel(&self, task: Task, seconds: u64) {
-        if let Ok(_mutex) = self.mutex.lock() {
-            if let Some(minute_time_wheel) = unsafe { self.minute_time_wheel.as_mut() } {
-                let mut target_pointer = minute_time_wheel.pointer + seconds as usize / 60;
-                target_pointer = target_pointer % minute_time_wheel.slot.len();
-                let _ = &minute_time_wheel.slot[target_pointer as usize].push_back(task);
+    /// 调用方需要持有`mutex`，返回任务放入的时间轮名称
+    fn push_T_without_lock(&self, task: Task, milliseconds: u64) -> &'static str {
+        for (layer, time_wheel) in self.time_wheels() {
+            if let Some(time_wheel) = unsafe { time_wheel.as_mut() }
+                && let Some(offset) = time_wheel.slot_offset(milliseconds)
+            {
+                time_wheel.push(task, offset);
+                return layer;
             }
         }
+        if let Some(overflow_queue) = unsafe { self.overflow_queue.as_mut() } {
+            let target_datetime = self.now() + Duration::from_millis(milliseconds);
+            overflow_queue
+                .entry(target_datetime)
+                .or_default()
+                .push(task);
+        }
+        "overflow"
     }
 
-    fn push_T_to_hour_time_wheel(&self, task: Task, seconds: u64) {
-        if let Ok(_mutex) = self.mutex.lock() {
-            if let Some(hour_time_wheel) = unsafe { self.hour_time_wheel.as_mut() } {
-                let mut target_pointer = hour_time_wheel.pointer + seconds as usize / 60 / 60;
-                if target_pointer >= hour_time_wheel.slot.len() {
-                    target_pointer = hour_time_wheel.slot.len() - 1;
-                } else {
-                    target_pointer = target_pointer % hour_time_wheel.slot.len();
-                }
-                let _ = &hour_time_wheel.slot[target_pointer as usize].push_back(task);
+    /// 调用方需要持有`mutex`，将溢出队列中进入小时时间轮范围的任务放入时间轮
+    fn drain_overflow_queue(&self) {
+        let (Some(overflow_queue), Some(hour_time_wheel)) =
+            (unsafe { self.overflow_queue.as_mut() }, unsafe {
+                self.hour_time_wheel.as_ref()
+            })
+        else {
+            return;
+        };
+        let now_time = self.now();
+        while let Some(entry) = overflow_queue.first_entry() {
+            let milliseconds = entry
+                .key()
+                .signed_duration_since(now_time)
+                .num_milliseconds()
+                .max(0) as u64;
+            if hour_time_wheel.slot_offset(milliseconds).is_none() {
+                break;
+            }
+            for task in entry.remove() {
+                self.push_T_without_lock(task, milliseconds);
             }
         }
     }
 
-    pub(crate) async fn tick(
-        &self,
-        detal: u64,
-        notice_list: Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>>,
-    ) -> Vec<Arc<Box<dyn ITaskHandler>>> {
-        let millisecond_time_wheel = unsafe { self.millisecond_time_wheel.as_mut().unwrap() };
-        let second_time_wheel = unsafe { self.second_time_wheel.as_mut().unwrap() };
-        let minute_time_wheel = unsafe { self.minute_time_wheel.as_mut().unwrap() };
-        let hour_time_wheel = unsafe { self.hour_time_wheel.as_mut().unwrap() };
-        millisecond_time_wheel.tick(detal);
-        second_time_wheel.tick(detal);
-        minute_time_wheel.tick(detal);
-        hour_time_wheel.tick(detal);
-
+    pub(crate) async fn tick(&self, detal: u64, notice_list: NoticeList) -> Vec<TaskRun> {
         let mut return_result = vec![];
-
-        if millisecond_time_wheel.interval_finished() {
-            self.check_time_wheel_result(
-                millisecond_time_wheel.check(),
-                &mut return_result,
-                notice_list.clone(),
-            )
-            .await;
-        }
-        if second_time_wheel.interval_finished() {
-            self.check_time_wheel_result(
-                second_time_wheel.check(),
-                &mut return_result,
-                notice_list.clone(),
-            )
-            .await;
-        }
-        if minute_time_wheel.interval_finished() {
-            self.check_time_wheel_result(
-                minute_time_wheel.check(),
-                &mut return_result,
-                notice_list.clone(),
-            )
-            .await;
-        }
-        if hour_time_wheel.interval_finished() {
+        for (layer, time_wheel_result) in self.tick_time_wheels(detal) {
             self.check_time_wheel_result(
-                hour_time_wheel.check(),
+                layer,
+                time_wheel_result,
                 &mut return_result,
                 notice_list.clone(),
             )
             .await;
         }
-
         return_result
     }
 
+    /// 在锁内推进各层时间轮，返回各层到期槽位中的任务
+    fn tick_time_wheels(&self, detal: u64) -> Vec<(&'static str, Vec<Task>)> {
+        let mut time_wheel_results = vec![];
+        if let Ok(_mutex) = self.mutex.lock() {
+            // 先放入溢出队列中的任务，推进时间较长时已经到期的任务可以在本次tick触发
+            self.drain_overflow_queue();
+            for (layer, time_wheel) in self.time_wheels() {
+                if let Some(time_wheel) = unsafe { time_wheel.as_mut() } {
+                    time_wheel_results.push((layer, time_wheel.tick(detal)));
+                }
+            }
+        }
+        time_wheel_results
+    }
+
     pub(crate) async fn check_time_wheel_result(
         &self,
+        layer: &'static str,
         mut time_wheel_result: Vec<Task>,
-        return_result: &mut Vec<Arc<Box<dyn ITaskHandler>>>,
-        notice_list: Arc<RwLock<Option<Vec<(usize, TaskStatus)>>>>,
+        return_result: &mut Vec<TaskRun>,
+        notice_list: NoticeList,
     ) {
-        let now_time = Local::now();
-        loop {
-            if let Some(mut t) = time_wheel_result.pop() {
-                {
-                    let mut mutex = notice_list.write().await;
-                    if let Some(vec) = mutex.as_mut() {
-                        for index in 0..vec.len() {
-                            let (id, status) = &vec[index];
-                            if *id == t.get_id() {
-                                t.set_status(status.clone());
-                                vec.remove(index);
-                                break;
-                            }
+        let now_time = self.now();
+        while let Some(mut t) = time_wheel_result.pop() {
+            if t.is_retry() {
+                self.check_retry_task(layer, t, return_result).await;
+                continue;
+            }
+            {
+                let mut mutex = notice_list.write().await;
+                if let Some(vec) = mutex.as_mut() {
+                    for index in 0..vec.len() {
+                        let (id, status) = &vec[index];
+                        if *id == t.get_id() {
+                            t.set_status(status.clone());
+                            vec.remove(index);
+                            break;
                         }
                     }
                 }
-                if !t.is_running() {
-                    if let Some(next_time) = t.get_next_datetime().await {
-                        let time_delta = next_time.signed_duration_since(now_time);
-                        let milliseconds = time_delta.num_milliseconds();
-                        t.set_target_date_time(next_time).await;
-                        self.push_T_to_time_wheel(t, milliseconds);
-                    }
-                    continue;
+            }
+            if !t.is_running() {
+                // 暂停中的任务不与TaskActor通信，定期检查是否恢复；已销毁的任务直接丢弃
+                if let TaskStatus::Pause = t.get_status() {
+                    self.push_T_to_time_wheel(t, DEFAULT_PAUSE_CHECK_TIME);
                 }
-                if let Some(target_datetime) = t.get_target_date_time().await {
-                    let milliseconds = target_datetime
-                        .signed_duration_since(now_time)
-                        .num_milliseconds();
-                    if milliseconds <= 100 {
+                continue;
+            }
+            if let Some(target_datetime) = t.get_target_date_time().await {
+                let milliseconds = target_datetime
+                    .signed_duration_since(now_time)
+                    .num_milliseconds();
+                if milliseconds <= self.precision {
+                    let policy = t.get_misfire_policy();
+                    let misfired = self.check_misfire(
+                        t.get_id(),
+                        target_datetime,
+                        t.get_misfire_threshold(),
+                        policy,
+                    );
+                    if misfired && policy == MisfirePolicy::Skip {
+                        self.skip_misfired(t, now_time).await;
+                        continue;
+                    }
+                    if t.check_overlap() {
+                        let context = t.next_context(target_datetime);
+                        self.send_fired_event(t.get_id(), target_datetime);
+                        if t.get_fixed_delay().is_some() {
+                            self.dispatch_fixed_delay(t, context, return_result).await;
+                            continue;
+                        }
                         match t.get_task_order_type() {
-                            super::task_actor::TaskOrderType::Order => {
-                                let _ =
-                                    t.get_task_signal_sender().send(TaskSignal::RunHandle).await;
-                            }
-                            super::task_actor::TaskOrderType::Disorder => {
-                                if let Some(handle) = t.get_handle().await {
-                                    return_result.push(handle);
-                                }
+                            TaskOrderType::Order => {
+                                let _ = t
+                                    .get_task_signal_sender()
+                                    .send(TaskSignal::RunHandle(context))
+                                    .await;
                             }
-                        }
-                        if let Some(result) = t.tick_repeat_model().await {
-                            if result {
-                                if let Some(next_time) = t.get_next_datetime().await {
-                                    let time_delta = next_time.signed_duration_since(now_time);
-                                    let milliseconds = time_delta.num_milliseconds();
-                                    t.set_target_date_time(next_time).await;
-                                    self.push_T_to_time_wheel(t, milliseconds);
+                            TaskOrderType::Disorder => {
+                                if let Some(task_run) = t.get_task_run(context).await {
+                                    return_result.push(task_run);
                                 }
                             }
                         }
                     } else {
-                        // 降级
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(task_id = t.get_id(), scheduled = %target_datetime, "task fire skipped");
+                        self.events.send(SchedulerEvent::Skipped {
+                            task_id: t.get_id(),
+                            scheduled: target_datetime,
+                        });
+                    }
+                    if let Some(true) = t.tick_repeat_model().await
+                        && let Some(next_time) = t.get_next_datetime().await
+                    {
+                        let time_delta = next_time.signed_duration_since(now_time);
+                        let milliseconds = time_delta.num_milliseconds();
+                        t.set_target_date_time(next_time).await;
                         self.push_T_to_time_wheel(t, milliseconds);
                     }
+                } else {
+                    self.demote_T(t, milliseconds, layer);
+                }
+            }
+        }
+    }
+
+    /// 跳过错过的触发，本次触发计入重复次数，从当前时间开始计算下一次触发时间
+    async fn skip_misfired(&self, mut t: Task, now_time: DateTime<Local>) {
+        if let Some(true) = t.tick_repeat_model().await {
+            let next_time = match t.get_fixed_delay() {
+                Some(delay) => TimeDelta::from_std(delay)
+                    .ok()
+                    .and_then(|delay| now_time.checked_add_signed(delay)),
+                None => t.get_next_datetime().await,
+            };
+            if let Some(next_time) = next_time {
+                let milliseconds = next_time.signed_duration_since(now_time).num_milliseconds();
+                t.set_target_date_time(next_time).await;
+                self.push_T_to_time_wheel(t, milliseconds);
+            }
+        }
+    }
+
+    /// 固定延迟的任务在运行结束后才能计算下一次触发时间，由本次运行负责将任务放回时间轮
+    async fn dispatch_fixed_delay(
+        &self,
+        mut t: Task,
+        context: TaskContext,
+        return_result: &mut Vec<TaskRun>,
+    ) {
+        let Some(mut task_run) = t.get_task_run(context).await else {
+            return;
+        };
+        let task_order_type = t.get_task_order_type();
+        let task_signal_sender = t.get_task_signal_sender();
+        if let Some(true) = t.tick_repeat_model().await {
+            task_run.set_reschedule(t, self.weak_self.clone());
+        }
+        match task_order_type {
+            TaskOrderType::Order => {
+                let _ = task_signal_sender.send(TaskSignal::RunTask(task_run)).await;
+            }
+            TaskOrderType::Disorder => return_result.push(task_run),
+        }
+    }
+
+    /// 检查一次性的重试任务，到期后运行一次，不影响任务原有的cron节奏
+    async fn check_retry_task(
+        &self,
+        layer: &'static str,
+        mut t: Task,
+        return_result: &mut Vec<TaskRun>,
+    ) {
+        let Some(task_run) = t.get_retry_run() else {
+            return;
+        };
+        match task_run.get_task_status() {
+            // 已完成的任务仍然重试最后一次失败的运行
+            TaskStatus::Running | TaskStatus::Completed => {
+                let milliseconds = task_run
+                    .get_context()
+                    .scheduled_time()
+                    .signed_duration_since(self.now())
+                    .num_milliseconds();
+                if milliseconds > self.precision {
+                    self.demote_T(t, milliseconds, layer);
+                    return;
+                }
+                let Some(mut task_run) = t.take_retry_run() else {
+                    return;
+                };
+                task_run.set_pending();
+                let scheduled = task_run.get_context().scheduled_time();
+                // 重试总是运行，错过触发只用于告警
+                self.check_misfire(t.get_id(), scheduled, None, MisfirePolicy::FireOnce);
+                self.send_fired_event(t.get_id(), scheduled);
+                match t.get_task_order_type() {
+                    TaskOrderType::Order => {
+                        let _ = t
+                            .get_task_signal_sender()
+                            .send(TaskSignal::RunTask(task_run))
+                            .await;
+                    }
+                    TaskOrderType::Disorder => return_result.push(task_run),
                 }
-            } else {
-                break;
             }
+            TaskStatus::Pause => self.push_T_to_time_wheel(t, DEFAULT_PAUSE_CHECK_TIME),
+            TaskStatus::Destory => {}
+        }
+    }
+}
+
+impl Drop for TierTimeWheel {
+    fn drop(&mut self) {
+        for (_, time_wheel) in self.time_wheels() {
+            drop(unsafe { Box::from_raw(time_wheel) });
         }
+        drop(unsafe { Box::from_raw(self.overflow_queue) });
     }
 }
 
@@ -257,32 +460,52 @@ impl TimeWheel {
         Self {
             slot,
             pointer: 0,
-            interval: interval,
+            interval,
             interval_setting: interval,
         }
     }
 
-    pub(crate) fn tick(&mut self, detal: u64) {
-        self.interval = self.interval.saturating_sub(detal);
+    /// 推进`detal`毫秒，返回期间经过的所有槽位中的任务
+    /// 单次推进超过一圈时，每个槽位只需检查一次
+    pub(crate) fn tick(&mut self, detal: u64) -> Vec<Task> {
+        let mut tasks_vec = vec![];
+        if detal < self.interval {
+            self.interval -= detal;
+            return tasks_vec;
+        }
+        let detal = detal - self.interval;
+        let passed = 1 + detal / self.interval_setting;
+        let start_pointer = self.pointer;
+        for _ in 0..passed.min(self.slot.len() as u64) {
+            tasks_vec.append(&mut self.check());
+        }
+        self.pointer =
+            (start_pointer + (passed % self.slot.len() as u64) as usize) % self.slot.len();
+        self.interval = self.interval_setting - detal % self.interval_setting;
+        tasks_vec
+    }
+
+    /// 距离目标时间`milliseconds`的任务应放入的槽位偏移量，超出本层范围时返回`None`
+    /// 当前指针所在的槽位会在`interval`毫秒后被检查，之后每个槽位间隔`interval_setting`毫秒
+    fn slot_offset(&self, milliseconds: u64) -> Option<usize> {
+        let offset = milliseconds.saturating_sub(self.interval) / self.interval_setting;
+        (offset < self.slot.len() as u64).then_some(offset as usize)
     }
 
-    pub(crate) fn interval_finished(&mut self) -> bool {
-        if self.interval <= 0 {
-            self.interval = self.interval_setting;
-            true
-        } else {
-            false
-        }
+    #[cfg(feature = "metrics")]
+    fn len(&self) -> usize {
+        self.slot.iter().map(VecDeque::len).sum()
+    }
+
+    fn push(&mut self, task: Task, offset: usize) {
+        let target_pointer = (self.pointer + offset) % self.slot.len();
+        self.slot[target_pointer].push_back(task);
     }
 
     pub(crate) fn check(&mut self) -> Vec<Task> {
         let mut tasks_vec = vec![];
-        loop {
-            if let Some(t) = self.slot[self.pointer].pop_front() {
-                tasks_vec.push(t);
-            } else {
-                break;
-            }
+        while let Some(t) = self.slot[self.pointer].pop_front() {
+            tasks_vec.push(t);
         }
         self.pointer += 1;
         if self.pointer >= self.slot.len() {
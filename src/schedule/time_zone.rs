@@ -0,0 +1,145 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+
+/// ## 任务时区
+/// cron表达式按照任务所在的时区解析，如`0 0 9 * * ?`在`Asia/Shanghai`时区表示北京时间每天9点
+///
+/// 夏令时切换时：重复出现的本地时间只在第一次出现时触发，不存在的本地时间会被跳过
+///
+/// `Tz`只在开启`tz` feature时存在，匹配时需要保留`_`分支
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TaskTimeZone {
+    /// 系统本地时区
+    #[default]
+    Local,
+    /// UTC
+    Utc,
+    /// 固定偏移量的时区，如`+08:00`
+    Fixed(FixedOffset),
+    /// IANA时区，如`Asia/Shanghai`，需要开启`tz` feature
+    #[cfg(feature = "tz")]
+    Tz(chrono_tz::Tz),
+}
+
+impl TaskTimeZone {
+    /// 东(正)/西(负)偏移`hours`小时的固定时区，超出`-23..=23`时返回`None`
+    pub fn fixed_hours(hours: i32) -> Option<Self> {
+        FixedOffset::east_opt(hours * 60 * 60).map(TaskTimeZone::Fixed)
+    }
+
+    /// 将时间换算为该时区的时间
+    pub fn convert(&self, datetime: &DateTime<Local>) -> DateTime<FixedOffset> {
+        match self {
+            TaskTimeZone::Local => datetime.fixed_offset(),
+            TaskTimeZone::Utc => datetime.with_timezone(&Utc).fixed_offset(),
+            TaskTimeZone::Fixed(offset) => datetime.with_timezone(offset),
+            #[cfg(feature = "tz")]
+            TaskTimeZone::Tz(tz) => datetime.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    /// 计算`after`之后的下一次cron时间
+    pub(crate) fn next_after(
+        &self,
+        cron_schedule: &cron::Schedule,
+        after: &DateTime<Local>,
+    ) -> Option<DateTime<Local>> {
+        match self {
+            TaskTimeZone::Local => first_later(cron_schedule.after(after), after),
+            TaskTimeZone::Utc => {
+                first_later(cron_schedule.after(&after.with_timezone(&Utc)), after)
+            }
+            TaskTimeZone::Fixed(offset) => {
+                first_later(cron_schedule.after(&after.with_timezone(offset)), after)
+            }
+            #[cfg(feature = "tz")]
+            TaskTimeZone::Tz(tz) => {
+                first_later(cron_schedule.after(&after.with_timezone(tz)), after)
+            }
+        }
+    }
+}
+
+/// 夏令时结束后重复的一小时内，cron会交替返回同一本地时间的两次出现，其中可能早于`after`
+/// 这里跳过不晚于`after`的时间以及重复本地时间的第二次出现
+fn first_later<Tz: TimeZone>(
+    mut datetimes: impl Iterator<Item = DateTime<Tz>>,
+    after: &DateTime<Local>,
+) -> Option<DateTime<Local>> {
+    datetimes
+        .find(|datetime| {
+            datetime > after
+                && datetime
+                    .timezone()
+                    .from_local_datetime(&datetime.naive_local())
+                    .earliest()
+                    .is_none_or(|earliest| earliest == *datetime)
+        })
+        .map(|datetime| datetime.with_timezone(&Local))
+}
+
+impl FromStr for TaskTimeZone {
+    type Err = String;
+
+    /// 支持`Local`、`UTC`、`+08:00`形式的固定偏移量，开启`tz` feature后支持`Asia/Shanghai`形式的IANA时区
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(TaskTimeZone::Local);
+        }
+        if s.eq_ignore_ascii_case("utc") || s == "Z" {
+            return Ok(TaskTimeZone::Utc);
+        }
+        if let Ok(offset) = FixedOffset::from_str(s) {
+            return Ok(TaskTimeZone::Fixed(offset));
+        }
+        #[cfg(feature = "tz")]
+        if let Ok(tz) = chrono_tz::Tz::from_str(s) {
+            return Ok(TaskTimeZone::Tz(tz));
+        }
+        Err(format!("unknown time zone: {s}"))
+    }
+}
+
+impl Display for TaskTimeZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskTimeZone::Local => write!(f, "Local"),
+            TaskTimeZone::Utc => write!(f, "UTC"),
+            TaskTimeZone::Fixed(offset) => write!(f, "{offset}"),
+            #[cfg(feature = "tz")]
+            TaskTimeZone::Tz(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+#[cfg(feature = "tz")]
+impl From<chrono_tz::Tz> for TaskTimeZone {
+    fn from(tz: chrono_tz::Tz) -> Self {
+        TaskTimeZone::Tz(tz)
+    }
+}
+
+impl From<FixedOffset> for TaskTimeZone {
+    fn from(offset: FixedOffset) -> Self {
+        TaskTimeZone::Fixed(offset)
+    }
+}
+
+/// 按`Display`的字符串保存，读取时按`FromStr`解析
+#[cfg(feature = "persistence")]
+impl serde::Serialize for TaskTimeZone {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl<'de> serde::Deserialize<'de> for TaskTimeZone {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let time_zone = String::deserialize(deserializer)?;
+        TaskTimeZone::from_str(&time_zone).map_err(serde::de::Error::custom)
+    }
+}
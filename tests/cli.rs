@@ -0,0 +1,121 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// 运行`sundial`，返回退出是否成功以及stdout、stderr
+fn sundial(args: &[&str]) -> (bool, String, String) {
+    let Output {
+        status,
+        stdout,
+        stderr,
+    } = Command::new(env!("CARGO_BIN_EXE_sundial"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        status.success(),
+        String::from_utf8(stdout).unwrap(),
+        String::from_utf8(stderr).unwrap(),
+    )
+}
+
+/// 每个测试使用独立的任务定义文件
+fn jobs_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sundial-cli-{}-{name}.toml", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn validate_explains_the_fields_of_a_valid_expression() {
+    let (success, stdout, stderr) = sundial(&["validate", "0 30 9 * * ?"]);
+    assert!(success);
+    assert!(stdout.starts_with("`0 30 9 * * ?` is valid\n"));
+    assert!(stdout.contains("minutes       30"));
+    assert!(stdout.contains("hours         9"));
+    assert_eq!(stdout.matches("\n  20").count(), 3);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn validate_reports_an_invalid_expression_on_stderr() {
+    let (success, stdout, stderr) = sundial(&["validate", "0 61 * * * ?"]);
+    assert!(!success);
+    assert!(stdout.is_empty());
+    assert!(stderr.starts_with("error: `0 61 * * * ?` is invalid\n"));
+}
+
+#[test]
+fn next_prints_the_requested_number_of_fire_times_in_the_time_zone() {
+    let (success, stdout, stderr) = sundial(&["next", "0 0 9 * * ?", "-n", "4", "--tz", "UTC"]);
+    assert!(success);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|line| line.ends_with(" 09:00:00 +00:00")));
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn next_fails_when_the_expression_never_fires_again() {
+    let (success, stdout, stderr) = sundial(&["next", "0 0 0 1 1 ? 2000"]);
+    assert!(!success);
+    assert!(stdout.is_empty());
+    assert_eq!(stderr, "error: `0 0 0 1 1 ? 2000` never fires again\n");
+
+    let (success, stdout, stderr) = sundial(&["next", "not a cron"]);
+    assert!(!success);
+    assert!(stdout.is_empty());
+    assert!(stderr.starts_with("error: `not a cron` is invalid\n"));
+}
+
+#[test]
+fn check_lists_the_jobs_of_a_valid_file() {
+    let path = jobs_file(
+        "valid",
+        "[[jobs]]\nname = \"report\"\ncron = \"0 0 9 * * ?\"\nhandler = \"report\"\n\n\
+         [[jobs]]\nname = \"cleanup\"\ncron = \"0 0 * * * ?\"\nhandler = \"cleanup\"\n",
+    );
+    let (success, stdout, stderr) = sundial(&["check", path.to_str().unwrap()]);
+    assert!(success);
+    assert!(stdout.contains("  report"));
+    assert!(stdout.contains("  cleanup"));
+    assert!(stdout.ends_with(&format!("{}: 2 jobs ok\n", path.display())));
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn check_reports_every_problem_on_stderr() {
+    let path = jobs_file(
+        "invalid",
+        "[[jobs]]\nname = \"report\"\ncron = \"0 0 9 * * ?\"\nhandler = \"report\"\n\n\
+         [[jobs]]\nname = \"report\"\ncron = \"0 0 0 1 1 ? 2000\"\nhandler = \"report\"\n\n\
+         [[jobs]]\nname = \"broken\"\ncron = \"0 61 * * * ?\"\nhandler = \"broken\"\n",
+    );
+    let (success, stdout, stderr) = sundial(&["check", path.to_str().unwrap()]);
+    assert!(!success);
+    assert!(!stdout.contains("jobs ok"));
+    // cron的错误信息包含多行，只检查每个问题的开头
+    let problems: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.starts_with("error: "))
+        .collect();
+    assert_eq!(problems.len(), 3);
+    assert_eq!(problems[0], "error: report: duplicate job name");
+    assert!(problems[1].starts_with("error: broken: invalid cron `0 61 * * * ?`"));
+    assert_eq!(
+        problems[2],
+        "error: report: cron `0 0 0 1 1 ? 2000` never fires again"
+    );
+}
+
+#[test]
+fn check_reports_an_unreadable_file_on_stderr() {
+    let path = jobs_file("unparsable", "[[jobs]\n");
+    let (success, stdout, stderr) = sundial(&["check", path.to_str().unwrap()]);
+    assert!(!success);
+    assert!(stdout.is_empty());
+    assert!(stderr.starts_with(&format!("error: {}: ", path.display())));
+}
@@ -0,0 +1,175 @@
+//! 集成测试共用的任务函数与事件统计
+#![allow(dead_code)]
+
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Local, TimeZone};
+use lynn_sundial::schedule_api::*;
+use tokio::sync::{Semaphore, broadcast::Receiver};
+
+/// 计数任务函数返回的future
+pub type RunFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// 本地时间2026年的指定时刻
+pub fn local(month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2026, month, day, hour, min, sec)
+        .unwrap()
+}
+
+/// ## 运行计数
+/// 任务函数开始与结束时分别计数，并记录同时运行的最大数量
+#[derive(Clone, Default)]
+pub struct Counter {
+    started: Arc<AtomicUsize>,
+    finished: Arc<AtomicUsize>,
+    running: Arc<AtomicUsize>,
+    max_running: Arc<AtomicUsize>,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 立即结束的任务函数
+    pub fn handler(&self) -> impl Fn() -> RunFuture + Clone + Send + Sync + 'static {
+        self.sleeping(Duration::ZERO)
+    }
+
+    /// 运行`duration`后结束的任务函数，`duration`为真实时间
+    pub fn sleeping(
+        &self,
+        duration: Duration,
+    ) -> impl Fn() -> RunFuture + Clone + Send + Sync + 'static {
+        self.counted(move || {
+            Box::pin(async move {
+                if !duration.is_zero() {
+                    tokio::time::sleep(duration).await;
+                }
+            })
+        })
+    }
+
+    /// 取得`gate`的一个许可后才结束的任务函数，测试通过`Semaphore::add_permits`控制每次运行何时结束
+    pub fn gated(
+        &self,
+        gate: Arc<Semaphore>,
+    ) -> impl Fn() -> RunFuture + Clone + Send + Sync + 'static {
+        self.counted(move || {
+            let gate = gate.clone();
+            Box::pin(async move {
+                gate.acquire().await.unwrap().forget();
+            })
+        })
+    }
+
+    fn counted(
+        &self,
+        work: impl Fn() -> RunFuture + Clone + Send + Sync + 'static,
+    ) -> impl Fn() -> RunFuture + Clone + Send + Sync + 'static {
+        let counter = self.clone();
+        move || {
+            let counter = counter.clone();
+            let work = work();
+            Box::pin(async move {
+                counter.started.fetch_add(1, Ordering::SeqCst);
+                let running = counter.running.fetch_add(1, Ordering::SeqCst) + 1;
+                counter.max_running.fetch_max(running, Ordering::SeqCst);
+                work.await;
+                counter.running.fetch_sub(1, Ordering::SeqCst);
+                counter.finished.fetch_add(1, Ordering::SeqCst);
+            })
+        }
+    }
+
+    pub fn started(&self) -> usize {
+        self.started.load(Ordering::SeqCst)
+    }
+
+    pub fn finished(&self) -> usize {
+        self.finished.load(Ordering::SeqCst)
+    }
+
+    pub fn max_running(&self) -> usize {
+        self.max_running.load(Ordering::SeqCst)
+    }
+
+    /// 等待开始的运行次数达到`expected`，最多等待5秒，返回开始的运行次数
+    pub async fn wait_started(&self, expected: usize) -> usize {
+        let _ = tokio::time::timeout(Duration::from_secs(5), async {
+            while self.started() < expected {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        self.started()
+    }
+
+    /// 等待结束的运行次数达到`expected`，最多等待5秒，返回结束的运行次数
+    pub async fn wait_finished(&self, expected: usize) -> usize {
+        let _ = tokio::time::timeout(Duration::from_secs(5), async {
+            while self.finished() < expected {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        self.finished()
+    }
+}
+
+/// 取出已经收到的事件，返回满足`matches`的事件数量
+pub fn count_events(
+    events: &mut Receiver<SchedulerEvent>,
+    matches: impl Fn(&SchedulerEvent) -> bool,
+) -> usize {
+    let mut count = 0;
+    while let Ok(event) = events.try_recv() {
+        if matches(&event) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// 等待满足`matches`的事件数量达到`expected`，最多等待5秒，返回收到的数量
+/// 用于在副reactor中发送的事件，如`LimitBehavior`放弃运行与`OverlapPolicy::Replace`取消运行
+pub async fn wait_events(
+    events: &mut Receiver<SchedulerEvent>,
+    expected: usize,
+    matches: impl Fn(&SchedulerEvent) -> bool,
+) -> usize {
+    let mut count = 0;
+    let _ = tokio::time::timeout(Duration::from_secs(5), async {
+        while count < expected {
+            count += count_events(events, &matches);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await;
+    count
+}
+
+/// 取出已经收到的事件，返回`task_id`的触发时间
+pub fn fired_times(events: &mut Receiver<SchedulerEvent>, task_id: usize) -> Vec<DateTime<Local>> {
+    let mut fired = vec![];
+    while let Ok(event) = events.try_recv() {
+        if let SchedulerEvent::Fired {
+            task_id: id,
+            scheduled,
+            ..
+        } = event
+            && id == task_id
+        {
+            fired.push(scheduled);
+        }
+    }
+    fired
+}
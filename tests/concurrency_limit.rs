@@ -0,0 +1,107 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::Semaphore;
+
+use common::{Counter, local};
+
+/// 等待许可的运行不占用副reactor，其他任务仍然可以运行
+async fn limited_task_does_not_starve_others(behavior: LimitBehavior) {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = SchedulerBuilder::new()
+        .task_pool_size(2)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let limited = Counter::new();
+    let other = Counter::new();
+    let gate = Arc::new(Semaphore::new(0));
+    scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            limited.gated(gate.clone()),
+            RepeatModel::Repetition,
+            TaskOptions::new()
+                .overlap_policy(OverlapPolicy::Parallel)
+                .max_concurrent(1, behavior),
+        )
+        .unwrap();
+    scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            other.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new().overlap_policy(OverlapPolicy::Parallel),
+        )
+        .unwrap();
+    // 第一次运行一直不结束，之后的运行都在等待许可
+    for second in 1..=10 {
+        clock.advance(Duration::from_secs(1)).await;
+        assert_eq!(other.wait_finished(second).await, second);
+    }
+    assert_eq!(limited.started(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn wait_does_not_park_workers() {
+    limited_task_does_not_starve_others(LimitBehavior::Wait).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn coalesce_does_not_park_workers() {
+    limited_task_does_not_starve_others(LimitBehavior::Coalesce).await;
+}
+
+/// 第一次运行期间再触发两次，每次只放行一次运行，等待中的运行依次开始
+/// `Wait`运行3次，`Coalesce`只保留一次等待中的运行，运行2次并放弃1次
+async fn waiting_runs_continue_after_release(behavior: LimitBehavior, runs: usize) {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = Counter::new();
+    let gate = Arc::new(Semaphore::new(0));
+    scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            counter.gated(gate.clone()),
+            RepeatModel::Times(3),
+            TaskOptions::new()
+                .overlap_policy(OverlapPolicy::Parallel)
+                .max_concurrent(1, behavior),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(counter.wait_started(1).await, 1);
+    for _ in 0..2 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    let skipped = common::wait_events(&mut events, 3 - runs, |event| {
+        matches!(event, SchedulerEvent::Skipped { .. })
+    })
+    .await;
+    assert_eq!(skipped, 3 - runs);
+    for run in 1..=runs {
+        assert_eq!(counter.started(), run);
+        gate.add_permits(1);
+        assert_eq!(counter.wait_finished(run).await, run);
+        if run < runs {
+            assert_eq!(counter.wait_started(run + 1).await, run + 1);
+        }
+    }
+    assert_eq!(counter.started(), runs);
+    assert_eq!(counter.max_running(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn wait_runs_every_waiting_run() {
+    waiting_runs_continue_after_release(LimitBehavior::Wait, 3).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn coalesce_keeps_one_waiting_run() {
+    waiting_runs_continue_after_release(LimitBehavior::Coalesce, 2).await;
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::Semaphore;
+
+use common::local;
+
+/// 运行时间超过延迟时，下一次触发从运行结束时开始计算，运行期间不会再次触发
+#[tokio::test(flavor = "multi_thread")]
+async fn delay_is_measured_from_completion() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let gate = Arc::new(Semaphore::new(0));
+    let task_id = scheduler
+        .push_task_with_schedule(
+            Schedule::FixedDelay(Duration::from_secs(2)),
+            counter.gated(gate.clone()),
+            RepeatModel::Repetition,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    for _ in 0..2 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(counter.wait_started(1).await, 1);
+    // 运行5秒，超过2秒的延迟
+    for _ in 0..5 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(counter.started(), 1);
+    gate.add_permits(1);
+    assert_eq!(counter.wait_finished(1).await, 1);
+    // 00:00:07运行结束，00:00:09再次触发
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while scheduler.task_info(task_id).unwrap().next_fire != Some(local(1, 1, 0, 0, 9)) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(counter.started(), 1);
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(counter.wait_started(2).await, 2);
+    assert_eq!(
+        common::fired_times(&mut events, task_id),
+        vec![local(1, 1, 0, 0, 2), local(1, 1, 0, 0, 9)]
+    );
+    assert_eq!(counter.max_running(), 1);
+    gate.add_permits(1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
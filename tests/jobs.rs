@@ -0,0 +1,277 @@
+#![cfg(feature = "jobs")]
+
+use std::{path::PathBuf, time::Duration};
+
+use lynn_sundial::schedule_api::*;
+
+async fn noop() {}
+
+fn registry() -> HandlerRegistry {
+    HandlerRegistry::new().register("noop", noop)
+}
+
+/// 每个测试使用独立的任务定义文件
+fn jobs_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sundial-{}-{name}.toml", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn job(name: &str, cron: &str, tags: &str) -> String {
+    format!(
+        "[[jobs]]\nname = \"{name}\"\ncron = \"{cron}\"\nhandler = \"noop\"\ntags = [{tags}]\n\n"
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reload_applies_only_the_changes() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    let path = jobs_file(
+        "diff",
+        &[
+            job("kept", "0 0 * * * ?", ""),
+            job("updated", "0 0 * * * ?", ""),
+            job("recreated", "0 0 * * * ?", ""),
+            job("removed", "0 0 * * * ?", ""),
+        ]
+        .concat(),
+    );
+    let ids = scheduler.load_jobs(&path, &registry).await.unwrap();
+    let [kept, updated, recreated, removed] = ids[..] else {
+        panic!("expected 4 tasks, got {ids:?}");
+    };
+    assert!(scheduler.pause_task_by_id(updated).await);
+
+    std::fs::write(
+        &path,
+        [
+            job("kept", "0 0 * * * ?", ""),
+            job("updated", "0 30 * * * ?", ""),
+            job("recreated", "0 0 * * * ?", "\"reports\""),
+            job("added", "0 0 * * * ?", ""),
+        ]
+        .concat(),
+    )
+    .unwrap();
+    let diff = scheduler.reload_jobs(&path, &registry).await.unwrap();
+    let added = scheduler.task_id_by_name("added").unwrap();
+    let new_recreated = scheduler.task_id_by_name("recreated").unwrap();
+    assert_eq!(
+        diff,
+        JobsDiff {
+            added: vec![added],
+            updated: vec![updated],
+            recreated: vec![new_recreated],
+            removed: vec![removed],
+        }
+    );
+    assert_ne!(new_recreated, recreated);
+    assert_eq!(scheduler.task_id_by_name("kept"), Some(kept));
+    assert!(scheduler.task_info(removed).is_none());
+    assert!(scheduler.task_info(recreated).is_none());
+    assert_eq!(
+        scheduler.task_info(new_recreated).unwrap().tags,
+        vec!["reports".to_string()]
+    );
+    // 只有cron变化的任务保持暂停状态
+    let info = scheduler.task_info(updated).unwrap();
+    assert_eq!(info.status, TaskStatus::Pause);
+    assert_eq!(info.cron, "0 30 * * * ?");
+
+    // 没有变化时不修改任何任务
+    let diff = scheduler.reload_jobs(&path, &registry).await.unwrap();
+    assert_eq!(diff, JobsDiff::default());
+    assert_eq!(
+        scheduler.task_info(updated).unwrap().status,
+        TaskStatus::Pause
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_reload_keeps_loaded_tasks() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    let path = jobs_file("failed", &job("kept", "0 0 * * * ?", ""));
+    let ids = scheduler.load_jobs(&path, &registry).await.unwrap();
+    let mut events = scheduler.subscribe();
+
+    std::fs::write(
+        &path,
+        [
+            job("kept", "0 30 * * * ?", ""),
+            "[[jobs]]\nname = \"missing\"\ncron = \"0 0 * * * ?\"\nhandler = \"missing\"\n"
+                .to_string(),
+        ]
+        .concat(),
+    )
+    .unwrap();
+    assert!(scheduler.reload_jobs(&path, &registry).await.is_err());
+    assert!(matches!(
+        events.try_recv(),
+        Ok(SchedulerEvent::JobsReloadFailed { .. })
+    ));
+    assert_eq!(scheduler.list_tasks().len(), 1);
+    assert_eq!(scheduler.task_info(ids[0]).unwrap().cron, "0 0 * * * ?");
+    let _ = std::fs::remove_file(path);
+}
+
+/// 监视期间调度器仍然可用，调度器停止后`changed`返回`false`
+#[tokio::test(flavor = "multi_thread")]
+async fn watch_jobs_does_not_borrow_the_scheduler() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    let path = jobs_file("watch", &job("watched", "0 0 * * * ?", ""));
+    let mut watcher = scheduler.watch_jobs(&path, Duration::from_millis(20));
+    assert!(watcher.changed().await);
+    scheduler
+        .reload_jobs(watcher.path(), &registry)
+        .await
+        .unwrap();
+    scheduler
+        .push_task("0 0 * * * ?", noop, RepeatModel::Repetition)
+        .unwrap();
+    assert!(scheduler.task_id_by_name("watched").is_some());
+
+    std::fs::write(&path, job("renamed", "0 0 * * * ?", "")).unwrap();
+    let changed = tokio::time::timeout(Duration::from_secs(1), watcher.changed()).await;
+    assert_eq!(changed, Ok(true));
+    let diff = scheduler
+        .reload_jobs(watcher.path(), &registry)
+        .await
+        .unwrap();
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(scheduler.list_tasks().len(), 2);
+
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+    assert!(!watcher.changed().await);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn load_jobs_applies_the_job_fields() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    let path = std::env::temp_dir().join(format!("sundial-{}-fields.json", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"{"jobs": [
+            {"name": "cleanup", "cron": "0 0 3 * * ?", "handler": "noop",
+             "order_type": "disorder", "repeat": 2, "timezone": "+08:00", "tags": ["ops"]},
+            {"name": "report", "cron": "0 0 9 * * ?", "handler": "noop", "repeat": "once"}
+        ]}"#,
+    )
+    .unwrap();
+    let ids = scheduler.load_jobs(&path, &registry).await.unwrap();
+    assert_eq!(ids.len(), 2);
+    let cleanup = scheduler.task_info(ids[0]).unwrap();
+    assert_eq!(cleanup.name, "cleanup");
+    assert_eq!(cleanup.cron, "0 0 3 * * ?");
+    assert_eq!(cleanup.order_type, TaskOrderType::Disorder);
+    assert_eq!(cleanup.repeat_remaining, Some(2));
+    assert_eq!(cleanup.tags, vec!["ops".to_string()]);
+    let next_fire = cleanup.next_fire.unwrap();
+    assert_eq!(
+        TaskTimeZone::fixed_hours(8)
+            .unwrap()
+            .convert(&next_fire)
+            .format("%H:%M:%S")
+            .to_string(),
+        "03:00:00"
+    );
+    let report = scheduler.task_info(ids[1]).unwrap();
+    assert_eq!(report.order_type, TaskOrderType::Order);
+    assert_eq!(report.repeat_remaining, Some(1));
+    assert_eq!(scheduler.snapshot().len(), 2);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_jobs_file_adds_nothing() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    scheduler
+        .push_order_task_with_options(
+            "0 0 * * * ?",
+            noop,
+            RepeatModel::Repetition,
+            TaskOptions::new().name("taken"),
+        )
+        .unwrap();
+    let path = jobs_file(
+        "invalid",
+        &[
+            job("valid", "0 0 * * * ?", ""),
+            job("taken", "0 0 * * * ?", ""),
+            job("twice", "0 0 * * * ?", ""),
+            job("twice", "0 0 * * * ?", ""),
+            job("broken", "not a cron", ""),
+            "[[jobs]]\nname = \"unknown\"\ncron = \"0 0 * * * ?\"\nhandler = \"missing\"\n\n"
+                .to_string(),
+        ]
+        .concat(),
+    );
+    let error = scheduler
+        .load_jobs(&path, &registry)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("ERR_CODE(1008)"), "{error}");
+    for problem in [
+        "taken: task name already exists",
+        "twice: duplicate job name",
+        "broken: invalid cron `not a cron`",
+        "unknown: handler `missing` is not registered",
+    ] {
+        assert!(error.contains(problem), "expected `{problem}` in {error}");
+    }
+    assert!(!error.contains("valid:"), "{error}");
+    assert_eq!(scheduler.list_tasks().len(), 1);
+    assert_eq!(scheduler.task_id_by_name("valid"), None);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_load_rolls_back_added_jobs() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    let mut events = scheduler.subscribe();
+    // 第二个任务的cron在2000年之后不再触发，新增时才会失败
+    let path = jobs_file(
+        "rollback",
+        &[
+            job("first", "0 0 * * * ?", ""),
+            job("expired", "0 0 0 1 1 ? 2000", ""),
+        ]
+        .concat(),
+    );
+    let error = scheduler
+        .load_jobs(&path, &registry)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("ERR_CODE(1001)"), "{error}");
+    assert!(scheduler.list_tasks().is_empty());
+    assert_eq!(scheduler.task_id_by_name("first"), None);
+    let mut added = vec![];
+    let mut destroyed = vec![];
+    while let Ok(event) = events.try_recv() {
+        match event {
+            SchedulerEvent::TaskAdded { task_id } => added.push(task_id),
+            SchedulerEvent::Destroyed { task_id } => destroyed.push(task_id),
+            _ => {}
+        }
+    }
+    assert_eq!(added.len(), 1);
+    assert_eq!(destroyed, added);
+
+    // 失败的文件没有记录为已加载，修正后可以重新加载
+    std::fs::write(&path, job("first", "0 0 * * * ?", "")).unwrap();
+    let ids = scheduler.load_jobs(&path, &registry).await.unwrap();
+    assert_eq!(scheduler.task_id_by_name("first"), Some(ids[0]));
+    let diff = scheduler.reload_jobs(&path, &registry).await.unwrap();
+    assert!(diff.added.is_empty() && diff.removed.is_empty());
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "metrics")]
+
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::broadcast::Receiver;
+
+async fn succeed() {}
+
+async fn fail() -> Result<(), String> {
+    Err("boom".to_string())
+}
+
+/// 等待到成功、失败与取消的事件各收到一次
+async fn wait_run_results(events: &mut Receiver<SchedulerEvent>) {
+    let (mut completed, mut failed, mut cancelled) = (false, false, false);
+    while !(completed && failed && cancelled) {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("run results not received")
+            .unwrap();
+        match event {
+            SchedulerEvent::Completed { .. } => completed = true,
+            SchedulerEvent::Failed { .. } => failed = true,
+            SchedulerEvent::Cancelled { .. } => cancelled = true,
+            _ => {}
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prometheus_output_counts_completed_failed_and_cancelled_runs() {
+    let clock = TestClock::new(common::local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    scheduler
+        .push_task_with_schedule(
+            Schedule::After(Duration::from_secs(1)),
+            succeed,
+            RepeatModel::Times(1),
+            TaskOptions::new(),
+        )
+        .unwrap();
+    scheduler
+        .push_task_with_schedule(
+            Schedule::After(Duration::from_secs(1)),
+            fail,
+            RepeatModel::Times(1),
+            TaskOptions::new(),
+        )
+        .unwrap();
+    let counter = common::Counter::new();
+    scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            counter.sleeping(Duration::from_secs(60)),
+            RepeatModel::Repetition,
+            TaskOptions::new().overlap_policy(OverlapPolicy::Replace),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(counter.wait_started(1).await, 1);
+    // 第二次触发取消第一次仍在运行的运行
+    clock.advance(Duration::from_secs(1)).await;
+    wait_run_results(&mut events).await;
+
+    let snapshot = scheduler.metrics_snapshot();
+    assert_eq!(snapshot.fires_total, 4);
+    assert_eq!(snapshot.completions_total, 1);
+    assert_eq!(snapshot.failures_total, 1);
+    assert_eq!(snapshot.misfires_total, 0);
+    assert_eq!(snapshot.run_duration.count, 3);
+    assert!(snapshot.run_duration.sum < 1.0);
+    let output = snapshot.render_prometheus();
+    for line in [
+        "# TYPE lynn_sundial_fires_total counter",
+        "lynn_sundial_fires_total 4",
+        "lynn_sundial_completions_total 1",
+        "lynn_sundial_failures_total 1",
+        "lynn_sundial_misfires_total 0",
+        "# TYPE lynn_sundial_fire_lateness_seconds histogram",
+        "lynn_sundial_fire_lateness_seconds_bucket{le=\"0.005\"} 4",
+        "lynn_sundial_fire_lateness_seconds_bucket{le=\"+Inf\"} 4",
+        "lynn_sundial_fire_lateness_seconds_sum 0",
+        "lynn_sundial_fire_lateness_seconds_count 4",
+        "# TYPE lynn_sundial_run_duration_seconds histogram",
+        "lynn_sundial_run_duration_seconds_bucket{le=\"1\"} 3",
+        "lynn_sundial_run_duration_seconds_bucket{le=\"300\"} 3",
+        "lynn_sundial_run_duration_seconds_bucket{le=\"+Inf\"} 3",
+        "lynn_sundial_run_duration_seconds_count 3",
+    ] {
+        assert!(
+            output.lines().any(|output_line| output_line == line),
+            "missing `{line}` in\n{output}"
+        );
+    }
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
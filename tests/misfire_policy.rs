@@ -0,0 +1,95 @@
+mod common;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeZone};
+use lynn_sundial::schedule_api::*;
+
+fn local(h: u32, min: u32, sec: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2026, 1, 1, h, min, sec).unwrap()
+}
+
+struct Misfire {
+    runs: usize,
+    misfired: usize,
+    next_fire: Option<DateTime<Local>>,
+    repeat_remaining: Option<usize>,
+}
+
+/// 每10秒触发一次的任务，在00:00:05一次推进2分钟，错过00:00:10到00:02:00共12次触发
+async fn advance_past_misfires(options: TaskOptions) -> Misfire {
+    let clock = TestClock::new(local(0, 0, 5));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let task_id = scheduler
+        .push_order_task_with_options(
+            "0/10 * * * * ?",
+            counter.handler(),
+            RepeatModel::Times(100),
+            options,
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(120)).await;
+    // 补运行的触发在之后的tick中依次处理，推进的时间不超过下一次触发时间00:02:10
+    for _ in 0..20 {
+        clock.advance(Duration::from_millis(100)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let info = scheduler.task_info(task_id).unwrap();
+    let misfire = Misfire {
+        runs: counter.started(),
+        misfired: common::count_events(&mut events, |event| {
+            matches!(event, SchedulerEvent::Misfired { .. })
+        }),
+        next_fire: info.next_fire,
+        repeat_remaining: info.repeat_remaining,
+    };
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+    misfire
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fire_once_runs_once_and_resumes_from_now() {
+    let misfire =
+        advance_past_misfires(TaskOptions::new().misfire_policy(MisfirePolicy::FireOnce)).await;
+    assert_eq!(misfire.runs, 1);
+    assert_eq!(misfire.misfired, 1);
+    assert_eq!(misfire.next_fire, Some(local(0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(99));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fire_all_runs_every_missed_fire() {
+    let misfire =
+        advance_past_misfires(TaskOptions::new().misfire_policy(MisfirePolicy::FireAll)).await;
+    assert_eq!(misfire.runs, 12);
+    assert_eq!(misfire.misfired, 12);
+    assert_eq!(misfire.next_fire, Some(local(0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(88));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn skip_does_not_run_and_counts_the_missed_fire() {
+    let misfire =
+        advance_past_misfires(TaskOptions::new().misfire_policy(MisfirePolicy::Skip)).await;
+    assert_eq!(misfire.runs, 0);
+    assert_eq!(misfire.misfired, 1);
+    assert_eq!(misfire.next_fire, Some(local(0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(99));
+}
+
+/// 晚于计划时间不超过阈值时不视为错过触发，`Skip`的任务仍然运行
+#[tokio::test(flavor = "multi_thread")]
+async fn late_fires_within_threshold_are_not_misfires() {
+    let misfire = advance_past_misfires(
+        TaskOptions::new()
+            .misfire_policy(MisfirePolicy::Skip)
+            .misfire_threshold(Duration::from_secs(300)),
+    )
+    .await;
+    assert_eq!(misfire.runs, 1);
+    assert_eq!(misfire.misfired, 0);
+    assert_eq!(misfire.next_fire, Some(local(0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(99));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use common::{Counter, local};
+use lynn_sundial::schedule_api::*;
+
+const STEP: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 从2026-01-01 00:00:00开始每次推进6小时，经过`target`后再推进`extra`，返回触发时间与实际触发时间
+async fn advance_through(
+    schedule: Schedule,
+    repeat: RepeatModel,
+    target: DateTime<Local>,
+    extra: Duration,
+) -> (Vec<(DateTime<Local>, DateTime<Local>)>, usize) {
+    let start = local(1, 1, 0, 0, 0);
+    let clock = TestClock::new(start);
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = Counter::new();
+    let task_id = scheduler
+        .push_task_with_schedule(schedule, counter.handler(), repeat, TaskOptions::new())
+        .unwrap();
+    assert_eq!(
+        scheduler.task_info(task_id).unwrap().next_fire,
+        Some(target)
+    );
+
+    let end = target + extra;
+    while clock.now() < end {
+        clock.advance(STEP).await;
+    }
+    let mut fired = vec![];
+    while let Ok(event) = events.try_recv() {
+        if let SchedulerEvent::Fired {
+            scheduled, actual, ..
+        } = event
+        {
+            fired.push((scheduled, actual));
+        }
+    }
+    let runs = counter.wait_finished(fired.len()).await;
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+    (fired, runs)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fires_a_week_ahead() {
+    let target = local(1, 8, 0, 0, 0);
+    let (fired, runs) = advance_through(
+        Schedule::At(target),
+        RepeatModel::Once,
+        target,
+        Duration::from_secs(2 * 24 * 60 * 60),
+    )
+    .await;
+    assert_eq!(fired, vec![(target, target)]);
+    assert_eq!(runs, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fires_a_month_ahead() {
+    let target = local(2, 1, 0, 0, 0);
+    let (fired, runs) = advance_through(
+        "0 0 0 1 * ?".parse().unwrap(),
+        RepeatModel::Repetition,
+        target,
+        Duration::from_secs(7 * 24 * 60 * 60),
+    )
+    .await;
+    assert_eq!(fired, vec![(target, target)]);
+    assert_eq!(runs, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fires_a_year_ahead() {
+    let target = local(1, 1, 0, 0, 0) + chrono::Days::new(365);
+    let (fired, runs) = advance_through(
+        "0 0 0 1 1 ?".parse().unwrap(),
+        RepeatModel::Repetition,
+        target,
+        Duration::from_secs(30 * 24 * 60 * 60),
+    )
+    .await;
+    assert_eq!(fired, vec![(target, target)]);
+    assert_eq!(runs, 1);
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::Semaphore;
+
+use common::{Counter, local};
+
+/// 只有一个副reactor，00:00:01先用一次一直不结束的运行占住，之后新增的任务每秒触发一次，触发的运行只能排队
+async fn blocked_scheduler(
+    policy: OverlapPolicy,
+) -> (Scheduler, TestClock, Arc<Semaphore>, Counter, usize) {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = SchedulerBuilder::new()
+        .task_pool_size(1)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let blocker = Counter::new();
+    let gate = Arc::new(Semaphore::new(0));
+    scheduler
+        .push_task_with_schedule(
+            Schedule::After(Duration::from_secs(1)),
+            blocker.gated(gate.clone()),
+            RepeatModel::Times(1),
+            TaskOptions::new().overlap_policy(OverlapPolicy::Parallel),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(blocker.wait_started(1).await, 1);
+    let counter = Counter::new();
+    let task_id = scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new().overlap_policy(policy),
+        )
+        .unwrap();
+    (scheduler, clock, gate, counter, task_id)
+}
+
+/// 排队中尚未开始的运行也视为重叠，之后的触发被跳过
+#[tokio::test(flavor = "multi_thread")]
+async fn skip_sees_queued_runs() {
+    let (scheduler, clock, gate, counter, task_id) = blocked_scheduler(OverlapPolicy::Skip).await;
+    let mut events = scheduler.subscribe();
+    // 00:00:02到00:00:11触发10次，第一次排队，其余9次跳过
+    for _ in 0..10 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    let skipped = common::wait_events(
+        &mut events,
+        9,
+        |event| matches!(event, SchedulerEvent::Skipped { task_id: id, .. } if *id == task_id),
+    )
+    .await;
+    assert_eq!(skipped, 9);
+    assert_eq!(counter.started(), 0);
+    gate.add_permits(1);
+    assert_eq!(counter.wait_finished(1).await, 1);
+    assert_eq!(counter.started(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+/// 新的触发取消排队中尚未开始的运行，被取消的运行不再调用任务函数
+#[tokio::test(flavor = "multi_thread")]
+async fn replace_cancels_queued_runs() {
+    let (scheduler, clock, gate, counter, task_id) =
+        blocked_scheduler(OverlapPolicy::Replace).await;
+    let mut events = scheduler.subscribe();
+    // 00:00:02到00:00:11触发10次，前9次排队的运行依次被之后的触发取消
+    for _ in 0..10 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(counter.started(), 0);
+    // 被取消的运行在副reactor空闲后依次取出，此时才记为取消
+    gate.add_permits(1);
+    let cancelled = common::wait_events(
+        &mut events,
+        9,
+        |event| matches!(event, SchedulerEvent::Cancelled { task_id: id, .. } if *id == task_id),
+    )
+    .await;
+    assert_eq!(cancelled, 9);
+    assert_eq!(counter.wait_finished(1).await, 1);
+    assert_eq!(counter.started(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+/// 暂停前已经排队的运行结束后，恢复后的运行才开始
+#[tokio::test(flavor = "multi_thread")]
+async fn queue_runs_one_at_a_time_across_pause_and_resume() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let counter = Counter::new();
+    let task_id = scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            counter.sleeping(Duration::from_millis(300)),
+            RepeatModel::Repetition,
+            TaskOptions::new().overlap_policy(OverlapPolicy::Queue),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(counter.wait_started(1).await, 1);
+    assert!(scheduler.pause_task_by_id(task_id).await);
+    assert!(scheduler.restart_task_by_id(task_id).await);
+    // 暂停中的任务每隔1秒检查一次是否恢复
+    let mut events = scheduler.subscribe();
+    for _ in 0..2 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    let fired = common::fired_times(&mut events, task_id).len();
+    assert_eq!(fired, 1);
+    assert_eq!(counter.wait_finished(2).await, 2);
+    assert_eq!(counter.max_running(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
@@ -0,0 +1,293 @@
+#![cfg(feature = "persistence")]
+
+mod common;
+
+use std::{path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Local};
+use lynn_sundial::schedule_api::*;
+
+async fn noop() {}
+
+fn registry() -> HandlerRegistry {
+    HandlerRegistry::new().register("noop", noop)
+}
+
+fn store_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sundial-{}-{name}.json", std::process::id()))
+}
+
+/// 只在内存中保存快照的存储，用于构造无法恢复的快照
+struct MemoryStore(Vec<TaskSnapshot>);
+
+impl TaskStore for MemoryStore {
+    fn save(&self, _snapshots: &[TaskSnapshot]) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<TaskSnapshot>, Box<dyn std::error::Error>> {
+        Ok(self.0.clone())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn snapshot_round_trips_through_json_file_store() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    let running = scheduler
+        .push_registered_task(
+            "0 0 * * * ?".parse().unwrap(),
+            "noop",
+            &registry,
+            RepeatModel::Times(3),
+            TaskOptions::new()
+                .name("hourly")
+                .tags(["reports"])
+                .timeout(Duration::from_secs(30))
+                .max_concurrent(2, LimitBehavior::Coalesce)
+                .misfire_policy(MisfirePolicy::FireAll),
+        )
+        .unwrap();
+    let paused = scheduler
+        .push_registered_task(
+            Schedule::Every(Duration::from_secs(600)),
+            "noop",
+            &registry,
+            RepeatModel::Repetition,
+            TaskOptions::new().name("every"),
+        )
+        .unwrap();
+    assert!(scheduler.pause_task_by_id(paused).await);
+    let snapshots = scheduler.snapshot();
+    let store = JsonFileStore::new(store_path("round-trip"));
+    scheduler.save(&store).unwrap();
+    assert_eq!(store.load().unwrap(), snapshots);
+
+    let mut restored = Scheduler::new();
+    let task_ids = restored.restore(&store, &registry).await.unwrap();
+    assert_eq!(task_ids.len(), 2);
+    assert_eq!(restored.snapshot(), snapshots);
+    for (task_id, original) in task_ids.into_iter().zip([running, paused]) {
+        let info = restored.task_info(task_id).unwrap();
+        let original = scheduler.task_info(original).unwrap();
+        assert_eq!(info.name, original.name);
+        assert_eq!(info.tags, original.tags);
+        assert_eq!(info.status, original.status);
+        assert_eq!(info.repeat_remaining, original.repeat_remaining);
+        assert_eq!(info.next_fire, original.next_fire);
+    }
+    let _ = std::fs::remove_file(store.path());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn paused_snapshot_is_restored_without_running() {
+    let counter = common::Counter::new();
+    let registry = HandlerRegistry::new().register("counted", counter.handler());
+    let clock = TestClock::new(common::local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let task_id = scheduler
+        .push_registered_task(
+            Schedule::Every(Duration::from_secs(1)),
+            "counted",
+            &registry,
+            RepeatModel::Repetition,
+            TaskOptions::new().name("paused"),
+        )
+        .unwrap();
+    assert!(scheduler.pause_task_by_id(task_id).await);
+    let store = MemoryStore(scheduler.snapshot());
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+
+    let clock = TestClock::new(common::local(1, 1, 0, 0, 10));
+    let mut restored = Scheduler::with_clock(clock.clone());
+    let mut events = restored.subscribe();
+    let task_ids = restored.restore(&store, &registry).await.unwrap();
+    for _ in 0..5 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    let info = restored.task_info(task_ids[0]).unwrap();
+    assert_eq!(info.status, TaskStatus::Pause);
+    assert_eq!(info.name, "paused");
+    assert_eq!(counter.started(), 0);
+    assert_eq!(
+        common::count_events(&mut events, |event| matches!(
+            event,
+            SchedulerEvent::Fired { .. } | SchedulerEvent::Paused { .. }
+        )),
+        0
+    );
+
+    assert!(restored.restart_task_by_id(task_ids[0]).await);
+    for _ in 0..2 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert!(counter.wait_started(1).await >= 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_restore_restores_nothing() {
+    let registry = registry();
+    let mut scheduler = Scheduler::new();
+    scheduler
+        .push_registered_task(
+            "0 0 * * * ?".parse().unwrap(),
+            "noop",
+            &registry,
+            RepeatModel::Repetition,
+            TaskOptions::new().name("first"),
+        )
+        .unwrap();
+    scheduler
+        .push_registered_task(
+            "0 30 * * * ?".parse().unwrap(),
+            "noop",
+            &registry,
+            RepeatModel::Repetition,
+            TaskOptions::new().name("second"),
+        )
+        .unwrap();
+    let snapshots = scheduler.snapshot();
+
+    // 名称已经存在
+    let mut restored = Scheduler::new();
+    restored
+        .push_task("0 0 * * * ?", noop, RepeatModel::Repetition)
+        .unwrap();
+    let existing = restored
+        .push_order_task_with_options(
+            "0 0 * * * ?",
+            noop,
+            RepeatModel::Repetition,
+            TaskOptions::new().name("second"),
+        )
+        .unwrap();
+    let error = restored
+        .restore(&MemoryStore(snapshots.clone()), &registry)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("1006"), "{error}");
+    assert_eq!(restored.list_tasks().len(), 2);
+    assert!(restored.task_id_by_name("first").is_none());
+    assert_eq!(restored.task_id_by_name("second"), Some(existing));
+
+    // 不会再触发的任务
+    let mut exhausted = snapshots[1].clone();
+    exhausted.name = Some("exhausted".to_string());
+    exhausted.schedule = "0 0 0 1 1 ? 2000".parse().unwrap();
+    exhausted.next_fire = None;
+    let mut restored = Scheduler::new();
+    let error = restored
+        .restore(
+            &MemoryStore(vec![snapshots[0].clone(), exhausted]),
+            &registry,
+        )
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("1001"), "{error}");
+    assert!(restored.list_tasks().is_empty());
+
+    // 回滚后可以再次恢复
+    let task_ids = restored
+        .restore(&MemoryStore(snapshots.clone()), &registry)
+        .await
+        .unwrap();
+    assert_eq!(task_ids.len(), 2);
+    assert_eq!(restored.snapshot(), snapshots);
+}
+
+struct RestoredMisfire {
+    runs: usize,
+    misfired: usize,
+    next_fire: Option<DateTime<Local>>,
+    repeat_remaining: Option<usize>,
+}
+
+/// 每10秒触发一次的任务在00:00:05保存，下一次触发时间为00:00:10
+/// 00:02:05恢复时错过00:00:10到00:02:00共12次触发，恢复后按`policy`处理
+async fn restore_past_due(policy: MisfirePolicy) -> RestoredMisfire {
+    let counter = common::Counter::new();
+    let registry = HandlerRegistry::new().register("counted", counter.handler());
+    let mut scheduler = Scheduler::with_clock(TestClock::new(common::local(1, 1, 0, 0, 5)));
+    scheduler
+        .push_registered_task(
+            "0/10 * * * * ?".parse().unwrap(),
+            "counted",
+            &registry,
+            RepeatModel::Times(100),
+            TaskOptions::new().name("past-due").misfire_policy(policy),
+        )
+        .unwrap();
+    let snapshots = scheduler.snapshot();
+    assert_eq!(snapshots[0].next_fire, Some(common::local(1, 1, 0, 0, 10)));
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+
+    let clock = TestClock::new(common::local(1, 1, 0, 2, 5));
+    let mut restored = Scheduler::with_clock(clock.clone());
+    let mut events = restored.subscribe();
+    let task_id = restored
+        .restore(&MemoryStore(snapshots), &registry)
+        .await
+        .unwrap()[0];
+    // 补运行的触发在之后的tick中依次处理，推进的时间不超过下一次触发时间00:02:10
+    for _ in 0..20 {
+        clock.advance(Duration::from_millis(100)).await;
+    }
+    let (mut fired, mut misfired) = (0, 0);
+    while let Ok(event) = events.try_recv() {
+        match event {
+            SchedulerEvent::Fired { .. } => fired += 1,
+            SchedulerEvent::Misfired { .. } => misfired += 1,
+            _ => {}
+        }
+    }
+    let runs = counter.wait_finished(fired).await;
+    // 任务记录在TaskActor中同步，等待下一次触发时间晚于当前时间
+    let info = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let info = restored.task_info(task_id).unwrap();
+            if info
+                .next_fire
+                .is_some_and(|next_fire| next_fire > clock.now())
+            {
+                return info;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    restored.shutdown(ShutdownMode::Immediate).await;
+    RestoredMisfire {
+        runs,
+        misfired,
+        next_fire: info.next_fire,
+        repeat_remaining: info.repeat_remaining,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restored_past_due_task_fires_once() {
+    let misfire = restore_past_due(MisfirePolicy::FireOnce).await;
+    assert_eq!(misfire.runs, 1);
+    assert_eq!(misfire.misfired, 1);
+    assert_eq!(misfire.next_fire, Some(common::local(1, 1, 0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(99));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restored_past_due_task_fires_every_missed_fire() {
+    let misfire = restore_past_due(MisfirePolicy::FireAll).await;
+    assert_eq!(misfire.runs, 12);
+    assert_eq!(misfire.misfired, 12);
+    assert_eq!(misfire.next_fire, Some(common::local(1, 1, 0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(88));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restored_past_due_task_skips_the_missed_fires() {
+    let misfire = restore_past_due(MisfirePolicy::Skip).await;
+    assert_eq!(misfire.runs, 0);
+    assert_eq!(misfire.misfired, 1);
+    assert_eq!(misfire.next_fire, Some(common::local(1, 1, 0, 2, 10)));
+    assert_eq!(misfire.repeat_remaining, Some(99));
+}
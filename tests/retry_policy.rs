@@ -0,0 +1,77 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::mpsc::unbounded_channel;
+
+use common::local;
+
+/// 等待第`failures`次失败记录完成，失败记录后紧接着按当前时间计算重试时间，之后才能推进时钟
+async fn wait_failures(scheduler: &Scheduler, task_id: usize, failures: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while scheduler.task_stats(task_id).unwrap().failure_count < failures {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+    .await
+    .unwrap();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn retries_are_numbered_and_keep_the_cron_cadence() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let (sender, mut attempts) = unbounded_channel();
+    let task_id = scheduler
+        .push_order_task_with_options(
+            "0 * * * * ?",
+            // 每次尝试都失败，并发出本次运行的`(run_count, attempt, start_time)`
+            move |context: TaskContext| {
+                let _ = sender.send((context.run_count(), context.attempt(), context.start_time()));
+                async { Err::<(), _>("unavailable") }
+            },
+            RepeatModel::Repetition,
+            TaskOptions::new().retry(RetryPolicy::exponential(
+                3,
+                Duration::from_secs(10),
+                Duration::from_secs(15),
+            )),
+        )
+        .unwrap();
+    let mut received = vec![];
+    for _ in 0..150 {
+        clock.advance(Duration::from_secs(1)).await;
+        // 重试同样发送`Fired`事件，触发后等待本次尝试运行结束再推进时钟
+        if common::count_events(&mut events, |event| {
+            matches!(event, SchedulerEvent::Fired { .. })
+        }) > 0
+        {
+            let attempt = tokio::time::timeout(Duration::from_secs(5), attempts.recv())
+                .await
+                .expect("attempt not run")
+                .unwrap();
+            received.push(attempt);
+            wait_failures(&scheduler, task_id, received.len()).await;
+        }
+    }
+    // 第一次重试间隔10秒，第二次20秒超过上限，间隔15秒，第3次尝试后不再重试
+    assert_eq!(
+        received,
+        vec![
+            (1, 1, local(1, 1, 0, 1, 0)),
+            (1, 2, local(1, 1, 0, 1, 10)),
+            (1, 3, local(1, 1, 0, 1, 25)),
+            (2, 1, local(1, 1, 0, 2, 0)),
+            (2, 2, local(1, 1, 0, 2, 10)),
+            (2, 3, local(1, 1, 0, 2, 25)),
+        ]
+    );
+    assert!(attempts.try_recv().is_err());
+    let info = scheduler.task_info(task_id).unwrap();
+    assert_eq!(info.next_fire, Some(local(1, 1, 0, 3, 0)));
+    assert_eq!(scheduler.task_stats(task_id).unwrap().failure_count, 6);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
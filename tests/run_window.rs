@@ -0,0 +1,108 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// 每秒触发一次，只在00:00:05之后、00:00:08之前（含）触发
+fn window_task(scheduler: &mut Scheduler, counter: &common::Counter) -> usize {
+    scheduler
+        .push_task_with_schedule(
+            "* * * * * ?".parse().unwrap(),
+            counter.handler(),
+            RepeatModel::Until(local(1, 1, 0, 0, 8)),
+            TaskOptions::new()
+                .name("window")
+                .starting_at(local(1, 1, 0, 0, 5)),
+        )
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn no_fire_before_starting_at() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let task_id = window_task(&mut scheduler, &counter);
+    assert_eq!(
+        scheduler.task_info(task_id).unwrap().next_fire,
+        Some(local(1, 1, 0, 0, 6))
+    );
+    for _ in 0..5 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert!(common::fired_times(&mut events, task_id).is_empty());
+    assert_eq!(counter.started(), 0);
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(
+        common::fired_times(&mut events, task_id),
+        vec![local(1, 1, 0, 0, 6)]
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn last_fire_is_not_after_until() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let task_id = window_task(&mut scheduler, &counter);
+    for _ in 0..12 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(
+        common::fired_times(&mut events, task_id),
+        vec![
+            local(1, 1, 0, 0, 6),
+            local(1, 1, 0, 0, 7),
+            local(1, 1, 0, 0, 8)
+        ]
+    );
+    assert_eq!(counter.wait_finished(3).await, 3);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn task_completes_and_leaves_list_tasks_after_until() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let task_id = window_task(&mut scheduler, &counter);
+    for _ in 0..7 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(counter.wait_started(2).await, 2);
+    assert_eq!(
+        scheduler.task_info(task_id).unwrap().status,
+        TaskStatus::Running
+    );
+    clock.advance(Duration::from_secs(1)).await;
+    // 最后一次触发后TaskActor计算不出下一次触发时间，任务变为已完成
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("task not finished")
+            .unwrap();
+        if matches!(event, SchedulerEvent::Finished { task_id: id } if id == task_id) {
+            break;
+        }
+    }
+    assert!(scheduler.task_info(task_id).is_none());
+    assert!(scheduler.list_tasks().is_empty());
+    // 已完成的任务在下一次修改任务时移除，名称可以再次使用
+    scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new().name("window"),
+        )
+        .unwrap();
+    assert_eq!(scheduler.list_tasks().len(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// 构建失败时返回的错误信息
+fn build_error(builder: SchedulerBuilder) -> String {
+    match builder.build() {
+        Ok(_) => panic!("invalid config accepted"),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_configs_are_rejected() {
+    let millis = Duration::from_millis;
+    for (builder, reason) in [
+        (
+            SchedulerBuilder::new().tick_time(Duration::ZERO),
+            "tick time must be greater than 0",
+        ),
+        (
+            SchedulerBuilder::new().task_pool_size(0),
+            "task pool size must be greater than 0",
+        ),
+        (
+            SchedulerBuilder::new().channel_size(0),
+            "channel size must be greater than 0",
+        ),
+        (
+            SchedulerBuilder::new().event_channel_size(0),
+            "event channel size must be greater than 0",
+        ),
+        (
+            SchedulerBuilder::new().second_time_wheel(0, Duration::from_secs(1)),
+            "second time wheel must have at least one slot and a non-zero interval",
+        ),
+        (
+            SchedulerBuilder::new().millisecond_time_wheel(10, Duration::from_secs(1)),
+            "second time wheel interval must be greater than the millisecond time wheel interval",
+        ),
+        (
+            SchedulerBuilder::new().millisecond_time_wheel(5, millis(100)),
+            "millisecond time wheel must cover at least one second time wheel slot",
+        ),
+        (
+            SchedulerBuilder::new().tick_time(millis(200)),
+            "tick time must not be greater than the millisecond time wheel interval",
+        ),
+    ] {
+        let error = build_error(builder);
+        assert!(error.contains("ERR_CODE(1004)"), "{error}");
+        assert!(error.contains(reason), "expected `{reason}` in {error}");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn custom_config_runs_tasks() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = SchedulerBuilder::new()
+        .tick_time(Duration::from_millis(100))
+        .task_pool_size(2)
+        .channel_size(2)
+        .event_channel_size(16)
+        .hour_time_wheel(48, Duration::from_secs(60 * 60))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    // 48个槽位的小时时间轮直接容纳30小时之后的任务
+    let task_id = scheduler
+        .push_task_with_schedule(
+            Schedule::At(local(1, 2, 6, 0, 0)),
+            counter.handler(),
+            RepeatModel::Once,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    #[cfg(feature = "metrics")]
+    assert_eq!(scheduler.metrics_snapshot().wheel_occupancy.hour, 1);
+    for _ in 0..30 {
+        clock.advance(Duration::from_secs(60 * 60)).await;
+    }
+    assert_eq!(
+        common::fired_times(&mut events, task_id),
+        vec![local(1, 2, 6, 0, 0)]
+    );
+    assert_eq!(counter.wait_finished(1).await, 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
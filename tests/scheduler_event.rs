@@ -0,0 +1,165 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::broadcast::Receiver;
+
+use common::local;
+
+/// 接收之后的`n`个事件，运行时长清零以便比较
+async fn next_events(events: &mut Receiver<SchedulerEvent>, n: usize) -> Vec<SchedulerEvent> {
+    let mut received = Vec::with_capacity(n);
+    while received.len() < n {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("event not received")
+            .unwrap();
+        received.push(match event {
+            SchedulerEvent::Completed { task_id, .. } => SchedulerEvent::Completed {
+                task_id,
+                duration: Duration::ZERO,
+            },
+            event => event,
+        });
+    }
+    assert!(events.try_recv().is_err(), "unexpected extra events");
+    received
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn task_lifecycle_is_reported_in_order() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let task_id = scheduler
+        .push_task("* * * * * ?", || async {}, RepeatModel::Repetition)
+        .unwrap();
+    assert_eq!(
+        next_events(&mut events, 1).await,
+        vec![SchedulerEvent::TaskAdded { task_id }]
+    );
+
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(
+        next_events(&mut events, 2).await,
+        vec![
+            SchedulerEvent::Fired {
+                task_id,
+                scheduled: local(1, 1, 0, 0, 1),
+                actual: local(1, 1, 0, 0, 1),
+            },
+            SchedulerEvent::Completed {
+                task_id,
+                duration: Duration::ZERO,
+            },
+        ]
+    );
+
+    assert!(scheduler.pause_task_by_id(task_id).await);
+    scheduler
+        .update_cron_by_id(task_id, "*/5 * * * * ?")
+        .await
+        .unwrap();
+    assert!(scheduler.restart_task_by_id(task_id).await);
+    let received = next_events(&mut events, 3).await;
+    assert_eq!(received[0], SchedulerEvent::Paused { task_id });
+    assert!(
+        matches!(&received[1], SchedulerEvent::CronUpdated { task_id: id, cron } if *id == task_id && cron.contains("*/5")),
+        "unexpected {:?}",
+        received[1]
+    );
+    assert_eq!(received[2], SchedulerEvent::Resumed { task_id });
+
+    assert!(scheduler.trigger_now(task_id).await);
+    assert_eq!(
+        next_events(&mut events, 2).await,
+        vec![
+            SchedulerEvent::Triggered {
+                task_id,
+                actual: local(1, 1, 0, 0, 1),
+            },
+            SchedulerEvent::Completed {
+                task_id,
+                duration: Duration::ZERO,
+            },
+        ]
+    );
+
+    assert!(scheduler.destory_task_by_id(task_id).await);
+    assert_eq!(
+        next_events(&mut events, 1).await,
+        vec![SchedulerEvent::Destroyed { task_id }]
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn finished_and_failed_runs_are_reported() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let task_id = scheduler
+        .push_task(
+            "* * * * * ?",
+            || async { Err::<(), _>("boom") },
+            RepeatModel::Times(1),
+        )
+        .unwrap();
+    let mut events = scheduler.subscribe();
+    clock.advance(Duration::from_secs(1)).await;
+    let mut received = next_events(&mut events, 3).await;
+    // 最后一次触发后任务立即完成，与运行结果的先后顺序不确定
+    received.sort_by_key(|event| !matches!(event, SchedulerEvent::Fired { .. }));
+    assert_eq!(
+        received[0],
+        SchedulerEvent::Fired {
+            task_id,
+            scheduled: local(1, 1, 0, 0, 1),
+            actual: local(1, 1, 0, 0, 1),
+        }
+    );
+    assert!(received.contains(&SchedulerEvent::Finished { task_id }));
+    assert!(received.iter().any(|event| matches!(
+        event,
+        SchedulerEvent::Failed { task_id: id, error, .. } if *id == task_id && error == "boom"
+    )));
+    assert!(
+        received
+            .iter()
+            .all(|event| event.task_id() == Some(task_id))
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_subscriber_lags() {
+    let mut scheduler = SchedulerBuilder::new()
+        .event_channel_size(2)
+        .clock(TestClock::new(local(1, 1, 0, 0, 0)))
+        .build()
+        .unwrap();
+    let mut events = scheduler.subscribe();
+    let task_ids: Vec<usize> = (0..5)
+        .map(|_| {
+            scheduler
+                .push_task("* * * * * ?", || async {}, RepeatModel::Repetition)
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        events.try_recv(),
+        Err(tokio::sync::broadcast::error::TryRecvError::Lagged(3))
+    );
+    assert_eq!(
+        next_events(&mut events, 2).await,
+        vec![
+            SchedulerEvent::TaskAdded {
+                task_id: task_ids[3]
+            },
+            SchedulerEvent::TaskAdded {
+                task_id: task_ids[4]
+            },
+        ]
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{Counter, count_events, local};
+use lynn_sundial::schedule_api::*;
+
+/// 触发一次运行`duration`的任务，等待任务函数开始运行
+async fn started_scheduler(duration: Duration) -> (Scheduler, Counter) {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let counter = Counter::new();
+    scheduler
+        .push_task_with_schedule(
+            Schedule::After(Duration::from_secs(1)),
+            counter.sleeping(duration),
+            RepeatModel::Once,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    assert_eq!(counter.wait_started(1).await, 1);
+    (scheduler, counter)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drain_waits_for_running_tasks() {
+    let (scheduler, counter) = started_scheduler(Duration::from_millis(200)).await;
+    scheduler
+        .shutdown(ShutdownMode::Drain {
+            deadline: Duration::from_secs(10),
+        })
+        .await;
+    assert_eq!(counter.finished(), 1);
+    scheduler.wait_all().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drain_aborts_tasks_after_deadline() {
+    let (scheduler, counter) = started_scheduler(Duration::from_secs(60)).await;
+    let start = Instant::now();
+    scheduler
+        .shutdown(ShutdownMode::Drain {
+            deadline: Duration::from_millis(100),
+        })
+        .await;
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(counter.started(), 1);
+    assert_eq!(counter.finished(), 0);
+}
+
+/// 停止后不再触发任务，也不能再新增任务
+#[tokio::test(flavor = "multi_thread")]
+async fn no_fires_after_shutdown() {
+    let mut scheduler = Scheduler::new();
+    let mut events = scheduler.subscribe();
+    let counter = Counter::new();
+    scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_millis(20)),
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    counter.wait_finished(1).await;
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+    let _ = count_events(&mut events, |_| true);
+    let started = counter.started();
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(counter.started(), started);
+    assert_eq!(
+        count_events(&mut events, |event| matches!(
+            event,
+            SchedulerEvent::Fired { .. }
+        )),
+        0
+    );
+    assert!(
+        scheduler
+            .push_task("0 0 * * * ?", counter.handler(), RepeatModel::Once)
+            .is_err()
+    );
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// 任务函数共享的资源，记录每次运行的任务id与运行次数
+#[derive(Clone, Default)]
+struct RunLog(Arc<Mutex<Vec<(usize, usize)>>>);
+
+impl RunLog {
+    fn runs(&self) -> Vec<(usize, usize)> {
+        let mut runs = self.0.lock().unwrap().clone();
+        runs.sort();
+        runs
+    }
+}
+
+struct Missing;
+
+async fn record_run(log: Res<RunLog>, task_id: TaskId) {
+    log.0.lock().unwrap().push((*task_id, 0));
+}
+
+async fn needs_missing(_missing: Res<Missing>) {}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn handlers_receive_resources_and_task_params() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let log = RunLog::default();
+    scheduler.insert_resource(log.clone());
+    let order_id = scheduler
+        .push_task("* * * * * ?", record_run, RepeatModel::Times(2))
+        .unwrap();
+    let disorder_id = scheduler
+        .push_disorder_task_with_options(
+            "* * * * * ?",
+            |log: Res<RunLog>, context: TaskContext| async move {
+                log.0
+                    .lock()
+                    .unwrap()
+                    .push((context.task_id(), context.run_count()));
+            },
+            RepeatModel::Times(2),
+            TaskOptions::new(),
+        )
+        .unwrap();
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    let runs = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let runs = log.runs();
+            if runs.len() >= 4 {
+                return runs;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    // 两个任务共享同一个资源
+    assert_eq!(
+        runs,
+        vec![
+            (order_id, 0),
+            (order_id, 0),
+            (disorder_id, 1),
+            (disorder_id, 2)
+        ]
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn missing_resource_is_rejected_when_pushing() {
+    let mut scheduler = Scheduler::new();
+    let error = scheduler
+        .push_task("* * * * * ?", needs_missing, RepeatModel::Once)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("ERR_CODE(1003)"), "{error}");
+    assert!(error.contains("Missing"), "{error}");
+    assert!(scheduler.list_tasks().is_empty());
+
+    // 插入资源后可以新增
+    scheduler.insert_resource(Missing);
+    assert!(
+        scheduler
+            .push_task("* * * * * ?", needs_missing, RepeatModel::Once)
+            .is_ok()
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
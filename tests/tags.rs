@@ -0,0 +1,131 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// 新增每秒触发一次的任务
+fn push_tagged(
+    scheduler: &mut Scheduler,
+    counter: &common::Counter,
+    name: &str,
+    tags: &[&str],
+) -> usize {
+    scheduler
+        .push_order_task_with_options(
+            "* * * * * ?",
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new().name(name).tags(tags.iter().copied()),
+        )
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn names_are_unique_and_looked_up() {
+    let mut scheduler = Scheduler::with_clock(TestClock::new(local(1, 1, 0, 0, 0)));
+    let counter = common::Counter::new();
+    let invoices = push_tagged(&mut scheduler, &counter, "invoices", &["billing"]);
+    scheduler
+        .push_task("* * * * * ?", counter.handler(), RepeatModel::Repetition)
+        .unwrap();
+    assert_eq!(scheduler.task_id_by_name("invoices"), Some(invoices));
+    assert_eq!(scheduler.task_id_by_name("unknown"), None);
+    let error = scheduler
+        .push_order_task_with_options(
+            "* * * * * ?",
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new().name("invoices"),
+        )
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("ERR_CODE(1006)"), "{error}");
+    assert_eq!(scheduler.list_tasks().len(), 2);
+
+    // 销毁后名称可以再次使用
+    assert!(scheduler.destory_task_by_id(invoices).await);
+    assert_eq!(scheduler.task_id_by_name("invoices"), None);
+    let new_id = push_tagged(&mut scheduler, &counter, "invoices", &[]);
+    assert_eq!(scheduler.task_id_by_name("invoices"), Some(new_id));
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bulk_operations_only_touch_tagged_tasks() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let billing = common::Counter::new();
+    let reports = common::Counter::new();
+    let invoices = push_tagged(
+        &mut scheduler,
+        &billing,
+        "invoices",
+        &["billing", "nightly"],
+    );
+    let refunds = push_tagged(&mut scheduler, &billing, "refunds", &["billing"]);
+    let summary = push_tagged(&mut scheduler, &reports, "summary", &["reports"]);
+    assert!(scheduler.pause_by_tag("unknown").await.is_empty());
+
+    assert_eq!(
+        scheduler.pause_by_tag("billing").await,
+        vec![invoices, refunds]
+    );
+    for task_id in [invoices, refunds] {
+        assert_eq!(
+            scheduler.task_info(task_id).unwrap().status,
+            TaskStatus::Pause
+        );
+    }
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(reports.wait_finished(3).await, 3);
+    assert_eq!(billing.started(), 0);
+    assert!(common::fired_times(&mut events, invoices).is_empty());
+
+    assert!(
+        scheduler
+            .update_cron_by_tag("billing", "not a cron")
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        scheduler
+            .update_cron_by_tag("billing", "0 * * * * ?")
+            .await
+            .unwrap(),
+        vec![invoices, refunds]
+    );
+    assert_eq!(scheduler.task_info(refunds).unwrap().cron, "0 * * * * ?");
+    assert_eq!(scheduler.task_info(summary).unwrap().cron, "* * * * * ?");
+
+    assert_eq!(
+        scheduler.restart_by_tag("billing").await,
+        vec![invoices, refunds]
+    );
+    for _ in 0..60 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    // 恢复后按新的cron在00:01:00各触发一次
+    assert_eq!(
+        common::fired_times(&mut events, refunds),
+        vec![local(1, 1, 0, 1, 0)]
+    );
+    assert_eq!(billing.wait_finished(2).await, 2);
+
+    assert_eq!(scheduler.destory_by_tag("nightly").await, vec![invoices]);
+    assert_eq!(scheduler.destory_by_tag("billing").await, vec![refunds]);
+    assert_eq!(
+        scheduler
+            .list_tasks()
+            .iter()
+            .map(|task| task.id)
+            .collect::<Vec<_>>(),
+        vec![summary]
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
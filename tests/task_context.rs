@@ -0,0 +1,88 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+use common::local;
+
+/// 等待任务函数发出的下一个上下文
+async fn next_context(contexts: &mut UnboundedReceiver<TaskContext>) -> TaskContext {
+    tokio::time::timeout(Duration::from_secs(5), contexts.recv())
+        .await
+        .expect("task not run")
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn handler_receives_context_of_each_run() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let (sender, mut contexts) = unbounded_channel();
+    let task_id = scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(1)),
+            move |context: TaskContext| {
+                let sender = sender.clone();
+                async move {
+                    let _ = sender.send(context);
+                }
+            },
+            RepeatModel::Repetition,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    for run_count in 1..=3 {
+        clock.advance(Duration::from_secs(1)).await;
+        let context = next_context(&mut contexts).await;
+        let scheduled = local(1, 1, 0, 0, run_count as u32);
+        assert_eq!(context.task_id(), task_id);
+        assert_eq!(context.scheduled_time(), scheduled);
+        assert_eq!(context.start_time(), scheduled);
+        assert_eq!(context.run_count(), run_count);
+        assert_eq!(context.attempt(), 1);
+        assert!(!context.is_manual());
+    }
+
+    // 手动触发的运行不计入运行次数
+    assert!(scheduler.trigger_now(task_id).await);
+    let context = next_context(&mut contexts).await;
+    assert_eq!(context.task_id(), task_id);
+    assert_eq!(context.start_time(), local(1, 1, 0, 0, 3));
+    assert_eq!(context.run_count(), 0);
+    assert!(context.is_manual());
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn zero_argument_and_context_handlers_run_side_by_side() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let counter = common::Counter::new();
+    let (sender, mut contexts) = unbounded_channel();
+    scheduler
+        .push_task("* * * * * ?", counter.handler(), RepeatModel::Times(2))
+        .unwrap();
+    let disorder_id = scheduler
+        .push_disorder_task_with_options(
+            "* * * * * ?",
+            move |context: TaskContext| {
+                let sender = sender.clone();
+                async move {
+                    let _ = sender.send(context);
+                }
+            },
+            RepeatModel::Times(2),
+            TaskOptions::new(),
+        )
+        .unwrap();
+    for run_count in 1..=2 {
+        clock.advance(Duration::from_secs(1)).await;
+        let context = next_context(&mut contexts).await;
+        assert_eq!(context.task_id(), disorder_id);
+        assert_eq!(context.run_count(), run_count);
+    }
+    assert_eq!(counter.wait_finished(2).await, 2);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
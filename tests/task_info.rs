@@ -0,0 +1,126 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+fn hour(day: u32, hour: u32) -> chrono::DateTime<chrono::Local> {
+    local(1, day, hour, 0, 0)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn task_info_and_list_tasks_describe_tasks() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let counter = common::Counter::new();
+    let daily = scheduler
+        .push_order_task_with_options(
+            "0 0 9 * * ?",
+            counter.handler(),
+            RepeatModel::Times(3),
+            TaskOptions::new().name("daily").tags(["reports"]),
+        )
+        .unwrap();
+    let hourly = scheduler
+        .push_disorder_task_with_options(
+            "0 0 * * * ?",
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    assert!(scheduler.task_info(hourly + 1).is_none());
+    assert!(scheduler.upcoming(hourly + 1, 3).is_none());
+
+    let info = scheduler.task_info(daily).unwrap();
+    assert_eq!(info.id, daily);
+    assert_eq!(info.name, "daily");
+    assert_eq!(info.tags, vec!["reports".to_string()]);
+    assert_eq!(info.cron, "0 0 9 * * ?");
+    assert_eq!(info.order_type, TaskOrderType::Order);
+    assert_eq!(info.status, TaskStatus::Running);
+    assert_eq!(info.repeat_remaining, Some(3));
+    assert_eq!(info.next_fire, Some(hour(1, 9)));
+    assert_eq!(info.last_fire, None);
+    assert_eq!(info.last_result, None);
+
+    for _ in 0..9 {
+        clock.advance(Duration::from_secs(60 * 60)).await;
+    }
+    assert_eq!(counter.wait_finished(10).await, 10);
+    let info = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let info = scheduler.task_info(daily).unwrap();
+            if info.last_result.is_some() && info.next_fire == Some(hour(2, 9)) {
+                return info;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(info.repeat_remaining, Some(2));
+    assert_eq!(info.last_fire, Some(hour(1, 9)));
+    assert_eq!(info.last_result, Some(TaskRunResult::Success));
+
+    let tasks = scheduler.list_tasks();
+    assert_eq!(
+        tasks.iter().map(|task| task.id).collect::<Vec<_>>(),
+        vec![daily, hourly]
+    );
+    assert_eq!(tasks[1].order_type, TaskOrderType::Disorder);
+    assert_eq!(tasks[1].repeat_remaining, None);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn upcoming_is_limited_by_remaining_runs_and_until() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let times = scheduler
+        .push_task("0 0 9 * * ?", || async {}, RepeatModel::Times(3))
+        .unwrap();
+    let until = scheduler
+        .push_task_with_schedule(
+            Schedule::Every(Duration::from_secs(60 * 60)),
+            || async {},
+            RepeatModel::Until(local(1, 1, 3, 30, 0)),
+            TaskOptions::new(),
+        )
+        .unwrap();
+    assert_eq!(
+        scheduler.upcoming(times, 5).unwrap(),
+        vec![hour(1, 9), hour(2, 9), hour(3, 9)]
+    );
+    assert_eq!(
+        scheduler.upcoming(times, 2).unwrap(),
+        vec![hour(1, 9), hour(2, 9)]
+    );
+    assert_eq!(
+        scheduler.upcoming(until, 10).unwrap(),
+        vec![hour(1, 1), hour(1, 2), hour(1, 3)]
+    );
+    assert!(scheduler.upcoming(times, 0).unwrap().is_empty());
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn upcoming_of_paused_task_starts_from_now() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let task_id = scheduler
+        .push_task("0 0 * * * ?", || async {}, RepeatModel::Repetition)
+        .unwrap();
+    assert!(scheduler.pause_task_by_id(task_id).await);
+    for _ in 0..5 {
+        clock.advance(Duration::from_secs(60 * 60)).await;
+    }
+    // 恢复后不补触发暂停期间错过的时间点
+    assert_eq!(
+        scheduler.upcoming(task_id, 2).unwrap(),
+        vec![hour(1, 6), hour(1, 7)]
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
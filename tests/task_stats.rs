@@ -0,0 +1,85 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// 前两次运行失败，之后成功
+async fn flaky(context: TaskContext) -> Result<(), String> {
+    if context.run_count() < 3 {
+        Err(format!("run {} failed", context.run_count()))
+    } else {
+        Ok(())
+    }
+}
+
+async fn succeed() -> Result<(), std::io::Error> {
+    Ok(())
+}
+
+/// 等待任务的运行统计记录到`run_count`次运行
+async fn wait_stats(scheduler: &Scheduler, task_id: usize, run_count: usize) -> TaskStats {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let stats = scheduler.task_stats(task_id).unwrap();
+            if stats.run_count >= run_count {
+                return stats;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failures_are_recorded_and_queried() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let flaky_id = scheduler
+        .push_task("* * * * * ?", flaky, RepeatModel::Repetition)
+        .unwrap();
+    let healthy_id = scheduler
+        .push_task("* * * * * ?", succeed, RepeatModel::Repetition)
+        .unwrap();
+    assert!(scheduler.failing_tasks().is_empty());
+
+    for _ in 0..2 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    let stats = wait_stats(&scheduler, flaky_id, 2).await;
+    assert_eq!(stats.run_count, 2);
+    assert_eq!(stats.success_count, 0);
+    assert_eq!(stats.failure_count, 2);
+    assert_eq!(stats.timeout_count, 0);
+    assert_eq!(stats.consecutive_failures, 2);
+    assert_eq!(stats.last_error.as_deref(), Some("run 2 failed"));
+    assert_eq!(stats.last_error_time, Some(local(1, 1, 0, 0, 2)));
+    assert_eq!(
+        stats.last_result,
+        Some(TaskRunResult::Failed("run 2 failed".to_string()))
+    );
+    assert!(stats.is_failing());
+    let healthy = wait_stats(&scheduler, healthy_id, 2).await;
+    assert_eq!(healthy.success_count, 2);
+    assert!(!healthy.is_failing());
+    let failing: Vec<usize> = scheduler
+        .failing_tasks()
+        .into_iter()
+        .map(|(task_id, _)| task_id)
+        .collect();
+    assert_eq!(failing, vec![flaky_id]);
+
+    // 成功后不再视为失败，但保留最近一次的错误信息
+    clock.advance(Duration::from_secs(1)).await;
+    let stats = wait_stats(&scheduler, flaky_id, 3).await;
+    assert_eq!(stats.success_count, 1);
+    assert_eq!(stats.failure_count, 2);
+    assert_eq!(stats.consecutive_failures, 0);
+    assert_eq!(stats.last_error.as_deref(), Some("run 2 failed"));
+    assert_eq!(stats.last_result, Some(TaskRunResult::Success));
+    assert!(scheduler.failing_tasks().is_empty());
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
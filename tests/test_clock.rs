@@ -0,0 +1,92 @@
+mod common;
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// `advance`推进当前时间，并在休眠中的一方再次休眠后才返回
+#[tokio::test(flavor = "multi_thread")]
+async fn advance_moves_now_and_waits_for_the_next_sleep() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    assert_eq!(clock.now(), local(1, 1, 0, 0, 0));
+    let wakeups = Arc::new(AtomicUsize::new(0));
+    let reactor = tokio::spawn({
+        let clock = clock.clone();
+        let wakeups = wakeups.clone();
+        async move {
+            loop {
+                clock.sleep(Duration::from_millis(25)).await;
+                wakeups.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+    // 没有推进时休眠不会返回
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(wakeups.load(Ordering::SeqCst), 0);
+
+    clock.advance(Duration::from_secs(90)).await;
+    assert_eq!(clock.now(), local(1, 1, 0, 1, 30));
+    assert_eq!(wakeups.load(Ordering::SeqCst), 1);
+    clock.advance(Duration::from_secs(24 * 60 * 60)).await;
+    assert_eq!(clock.now(), local(1, 2, 0, 1, 30));
+    assert_eq!(wakeups.load(Ordering::SeqCst), 2);
+    reactor.abort();
+}
+
+/// 按小时推进一天，只有这一天内到期的任务运行，且运行次数与计划一致
+#[tokio::test(flavor = "multi_thread")]
+async fn advancing_a_day_runs_exactly_the_due_tasks() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let hourly = common::Counter::new();
+    let noon = common::Counter::new();
+    let monday = common::Counter::new();
+    let after = common::Counter::new();
+    let tomorrow = common::Counter::new();
+    for (schedule, counter) in [
+        ("0 0 * * * ?".parse().unwrap(), &hourly),
+        ("0 0 12 * * ?".parse().unwrap(), &noon),
+        // 2026-01-01为星期四
+        ("0 0 9 * * MON".parse().unwrap(), &monday),
+        (Schedule::After(Duration::from_secs(30 * 60)), &after),
+        (Schedule::At(local(1, 2, 0, 0, 1)), &tomorrow),
+    ] {
+        scheduler
+            .push_task_with_schedule(
+                schedule,
+                counter.handler(),
+                RepeatModel::Repetition,
+                TaskOptions::new(),
+            )
+            .unwrap();
+    }
+    for _ in 0..24 {
+        clock.advance(Duration::from_secs(60 * 60)).await;
+    }
+    assert_eq!(clock.now(), local(1, 2, 0, 0, 0));
+    assert_eq!(hourly.wait_finished(24).await, 24);
+    assert_eq!(noon.wait_finished(1).await, 1);
+    assert_eq!(after.wait_finished(1).await, 1);
+    assert_eq!(
+        common::count_events(&mut events, |event| matches!(
+            event,
+            SchedulerEvent::Fired { .. }
+        )),
+        26
+    );
+    assert_eq!(hourly.started(), 24);
+    assert_eq!(noon.started(), 1);
+    assert_eq!(monday.started(), 0);
+    assert_eq!(after.started(), 1);
+    assert_eq!(tomorrow.started(), 0);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
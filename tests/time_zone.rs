@@ -0,0 +1,103 @@
+#![cfg(feature = "tz")]
+
+mod common;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use lynn_sundial::schedule_api::*;
+
+fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Local> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0)
+        .unwrap()
+        .with_timezone(&Local)
+}
+
+#[test]
+fn fall_back_skips_the_repeated_hour() {
+    let schedule: Schedule = "0 0/10 * * * ?".parse().unwrap();
+    // 2026-11-01 01:05 EST，重复的一小时中第二次出现的01:05
+    let upcoming = schedule.upcoming(&New_York.into(), &utc(2026, 11, 1, 6, 5), 3);
+    assert_eq!(
+        upcoming,
+        vec![
+            utc(2026, 11, 1, 7, 0),
+            utc(2026, 11, 1, 7, 10),
+            utc(2026, 11, 1, 7, 20)
+        ]
+    );
+    // 2026-11-01 01:45 EDT，01:50 EDT之后不再触发01:00~01:50 EST
+    let upcoming = schedule.upcoming(&New_York.into(), &utc(2026, 11, 1, 5, 45), 3);
+    assert_eq!(
+        upcoming,
+        vec![
+            utc(2026, 11, 1, 5, 50),
+            utc(2026, 11, 1, 7, 0),
+            utc(2026, 11, 1, 7, 10)
+        ]
+    );
+}
+
+#[test]
+fn fall_back_fires_the_first_occurrence_once() {
+    let schedule: Schedule = "0 30 1 * * ?".parse().unwrap();
+    // 2026-11-01 00:00 EDT
+    let upcoming = schedule.upcoming(&New_York.into(), &utc(2026, 11, 1, 4, 0), 2);
+    // 01:30 EDT，之后是第二天的01:30 EST
+    assert_eq!(
+        upcoming,
+        vec![utc(2026, 11, 1, 5, 30), utc(2026, 11, 2, 6, 30)]
+    );
+}
+
+#[test]
+fn spring_forward_skips_the_missing_hour() {
+    let schedule: Schedule = "0 30 2 * * ?".parse().unwrap();
+    // 2026-03-08 00:00 EST，当天02:30不存在
+    let upcoming = schedule.upcoming(&New_York.into(), &utc(2026, 3, 8, 5, 0), 2);
+    assert_eq!(
+        upcoming,
+        vec![utc(2026, 3, 9, 6, 30), utc(2026, 3, 10, 6, 30)]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fall_back_does_not_fire_every_tick() {
+    let clock = TestClock::new(utc(2026, 11, 1, 6, 5));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let task_id = scheduler
+        .push_order_task_with_options(
+            "0 0/10 * * * ?",
+            counter.handler(),
+            RepeatModel::Repetition,
+            TaskOptions::new().time_zone(New_York),
+        )
+        .unwrap();
+    assert_eq!(
+        scheduler.task_info(task_id).unwrap().next_fire,
+        Some(utc(2026, 11, 1, 7, 0))
+    );
+    for _ in 0..20 {
+        clock.advance(Duration::from_millis(100)).await;
+    }
+    assert!(common::fired_times(&mut events, task_id).is_empty());
+
+    clock.advance(Duration::from_secs(55 * 60)).await;
+    assert_eq!(
+        common::fired_times(&mut events, task_id),
+        vec![utc(2026, 11, 1, 7, 0)]
+    );
+    assert_eq!(counter.wait_finished(1).await, 1);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while scheduler.task_info(task_id).unwrap().next_fire != Some(utc(2026, 11, 1, 7, 10)) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(counter.started(), 1);
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
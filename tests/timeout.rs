@@ -0,0 +1,131 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+use tokio::sync::broadcast::Receiver;
+
+use common::local;
+
+/// 第一次运行一直不结束，之后的运行立即结束
+async fn hangs_first_run(context: TaskContext) {
+    if context.run_count() == 1 {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+}
+
+/// 等待下一次运行结束的事件
+async fn next_result(events: &mut Receiver<SchedulerEvent>) -> SchedulerEvent {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let event = events.recv().await.unwrap();
+            if matches!(
+                event,
+                SchedulerEvent::Completed { .. } | SchedulerEvent::Failed { .. }
+            ) {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("run not finished")
+}
+
+/// 第一次运行超时记为`TimedOut`，第二次触发正常运行
+async fn assert_timed_out_then_completed(
+    scheduler: &Scheduler,
+    clock: &TestClock,
+    events: &mut Receiver<SchedulerEvent>,
+    task_id: usize,
+) {
+    clock.advance(Duration::from_secs(1)).await;
+    match next_result(events).await {
+        SchedulerEvent::Failed {
+            task_id: id, error, ..
+        } => {
+            assert_eq!(id, task_id);
+            assert_eq!(error, "task timed out after 50ms");
+        }
+        event => panic!("unexpected {event:?}"),
+    }
+
+    clock.advance(Duration::from_secs(1)).await;
+    let event = next_result(events).await;
+    assert!(
+        matches!(event, SchedulerEvent::Completed { task_id: id, .. } if id == task_id),
+        "unexpected {event:?}"
+    );
+    let stats = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let stats = scheduler.task_stats(task_id).unwrap();
+            if stats.run_count == 2 {
+                return stats;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(stats.timeout_count, 1);
+    assert_eq!(stats.failure_count, 1);
+    assert_eq!(stats.success_count, 1);
+    assert_eq!(stats.last_result, Some(TaskRunResult::Success));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn order_task_times_out_with_task_timeout() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let task_id = scheduler
+        .push_order_task_with_options(
+            "* * * * * ?",
+            hangs_first_run,
+            RepeatModel::Repetition,
+            TaskOptions::new().timeout(Duration::from_millis(50)),
+        )
+        .unwrap();
+    assert_timed_out_then_completed(&scheduler, &clock, &mut events, task_id).await;
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn disorder_task_times_out_with_default_timeout() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    scheduler.set_default_timeout(Some(Duration::from_millis(50)));
+    let task_id = scheduler
+        .push_disorder_task_with_options(
+            "* * * * * ?",
+            hangs_first_run,
+            RepeatModel::Repetition,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    assert_timed_out_then_completed(&scheduler, &clock, &mut events, task_id).await;
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn task_timeout_overrides_default_timeout() {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    scheduler.set_default_timeout(Some(Duration::from_millis(10)));
+    let task_id = scheduler
+        .push_order_task_with_options(
+            "* * * * * ?",
+            || tokio::time::sleep(Duration::from_millis(100)),
+            RepeatModel::Times(1),
+            TaskOptions::new().timeout(Duration::from_secs(5)),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(1)).await;
+    let event = next_result(&mut events).await;
+    assert!(
+        matches!(event, SchedulerEvent::Completed { task_id: id, .. } if id == task_id),
+        "unexpected {event:?}"
+    );
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use lynn_sundial::schedule_api::*;
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+
+/// 记录每个`task_run` span的`task_name`
+#[derive(Clone, Default)]
+struct TaskNames {
+    names: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct TaskNameVisitor(Option<String>);
+
+impl Visit for TaskNameVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "task_name" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl Subscriber for TaskNames {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        if span.metadata().name() == "task_run" {
+            let mut visitor = TaskNameVisitor(None);
+            span.record(&mut visitor);
+            if let (Some(name), Ok(mut names)) = (visitor.0, self.names.lock()) {
+                names.push(name);
+            }
+        }
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+async fn export_report() {}
+
+/// span的`task_name`与任务记录的名称一致
+#[tokio::test(flavor = "multi_thread")]
+async fn task_run_span_uses_the_task_name() {
+    let task_names = TaskNames::default();
+    tracing::subscriber::set_global_default(task_names.clone()).unwrap();
+    let mut scheduler = Scheduler::new();
+    let named = scheduler
+        .push_task_with_schedule(
+            Schedule::After(Duration::from_millis(100)),
+            export_report,
+            RepeatModel::Once,
+            TaskOptions::new().name("nightly-report"),
+        )
+        .unwrap();
+    let unnamed = scheduler
+        .push_task_with_schedule(
+            Schedule::After(Duration::from_millis(100)),
+            export_report,
+            RepeatModel::Once,
+            TaskOptions::new(),
+        )
+        .unwrap();
+    // 运行一次的任务运行结束后被移除，先读取任务名称
+    let mut expected = vec![
+        scheduler.task_info(named).unwrap().name,
+        scheduler.task_info(unnamed).unwrap().name,
+    ];
+    expected.sort();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let mut names = task_names.names.lock().unwrap().clone();
+    names.sort();
+    assert_eq!(names, expected);
+    assert!(names.contains(&"nightly-report".to_string()));
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
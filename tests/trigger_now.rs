@@ -0,0 +1,75 @@
+mod common;
+
+use std::time::Duration;
+
+use lynn_sundial::schedule_api::*;
+
+use common::local;
+
+/// 手动触发后下一次触发时间、剩余运行次数与cron节奏保持不变
+async fn trigger_keeps_schedule(overlap_policy: OverlapPolicy) {
+    let clock = TestClock::new(local(1, 1, 0, 0, 0));
+    let mut scheduler = Scheduler::with_clock(clock.clone());
+    let mut events = scheduler.subscribe();
+    let counter = common::Counter::new();
+    let task_id = scheduler
+        .push_task_with_schedule(
+            "*/10 * * * * ?".parse().unwrap(),
+            counter.handler(),
+            RepeatModel::Times(3),
+            TaskOptions::new().overlap_policy(overlap_policy),
+        )
+        .unwrap();
+    clock.advance(Duration::from_secs(3)).await;
+    let before = scheduler.task_info(task_id).unwrap();
+    assert_eq!(before.next_fire, Some(local(1, 1, 0, 0, 10)));
+    assert_eq!(before.repeat_remaining, Some(3));
+
+    assert!(scheduler.trigger_now(task_id).await);
+    assert_eq!(counter.wait_finished(1).await, 1);
+    let after = scheduler.task_info(task_id).unwrap();
+    assert_eq!(after.next_fire, before.next_fire);
+    assert_eq!(after.repeat_remaining, before.repeat_remaining);
+    assert_eq!(after.cron, before.cron);
+    assert_eq!(
+        common::count_events(&mut events, |event| matches!(
+            event,
+            SchedulerEvent::Triggered { .. }
+        )),
+        1
+    );
+
+    for _ in 0..17 {
+        clock.advance(Duration::from_secs(1)).await;
+    }
+    assert_eq!(
+        common::fired_times(&mut events, task_id),
+        vec![local(1, 1, 0, 0, 10), local(1, 1, 0, 0, 20)]
+    );
+    assert_eq!(counter.wait_finished(3).await, 3);
+    // TaskActor在交给runner运行之后才同步下一次触发时间，等待同步完成
+    let info = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let info = scheduler.task_info(task_id).unwrap();
+            if info.next_fire != Some(local(1, 1, 0, 0, 20)) {
+                return info;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(info.next_fire, Some(local(1, 1, 0, 0, 30)));
+    assert_eq!(info.repeat_remaining, Some(1));
+    scheduler.shutdown(ShutdownMode::Immediate).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trigger_now_keeps_order_task_schedule() {
+    trigger_keeps_schedule(OverlapPolicy::Queue).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trigger_now_keeps_disorder_task_schedule() {
+    trigger_keeps_schedule(OverlapPolicy::Parallel).await;
+}